@@ -0,0 +1,211 @@
+//!
+//! Derive macro for meme-id typed IDs.
+//!
+//! Use through `derive` feature of `meme-id` crate.
+//!
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+/// Schemes modules that encode a single value into a phrase.
+const SCHEMES: &[&str] = &[
+    "adjective_noun",
+    "adjective_noun_verb",
+    "complex_phrase",
+    "compound_phrase",
+    "double_phrase",
+    "ipv4",
+    "ipv6",
+    "mac",
+    "phrase",
+    "preposition_pronoun",
+    "punk",
+    "simple_phrase",
+    "snowflake",
+    "ulid",
+    "uuid",
+    "var_phrase",
+];
+
+enum Style {
+    Sentence,
+    Hyphenated,
+}
+
+/// Implements `Display`, `FromStr` and `Debug` for a single field tuple struct
+/// using phrases of the scheme module specified in `#[meme_id(scheme = "...")]` attribute.
+/// With `#[meme_id(serde)]` also implements `Serialize` that writes the same phrase as `Display`
+/// and `Deserialize` that decodes phrase same way as `FromStr`.
+/// It requires `serde` feature of meme-id.
+///
+/// `#[meme_id(style = "hyphenated")]` makes `Display` and `Serialize` write hyphenated phrase.
+/// Default style is `"sentence"`.
+///
+/// Field type must not be wider than IDs of the scheme,
+/// so `compound_phrase` fits `u32` but fails to compile for `u64`.
+#[proc_macro_derive(MemeId, attributes(meme_id))]
+pub fn derive_meme_id(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match derive(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let mut scheme = None;
+    let mut style = Style::Sentence;
+    let mut serde = false;
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("meme_id") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("scheme") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                if !SCHEMES.contains(&&*lit.value()) {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("Unknown scheme, expected one of {}", SCHEMES.join(", ")),
+                    ));
+                }
+                scheme = Some(syn::Ident::new(&lit.value(), lit.span()));
+                Ok(())
+            } else if meta.path.is_ident("style") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                style = match &*lit.value() {
+                    "sentence" => Style::Sentence,
+                    "hyphenated" => Style::Hyphenated,
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "Unknown style, expected \"sentence\" or \"hyphenated\"",
+                        ))
+                    }
+                };
+                Ok(())
+            } else if meta.path.is_ident("serde") {
+                if !cfg!(feature = "serde") {
+                    return Err(meta.error("`serde` requires `serde` feature of meme-id"));
+                }
+                serde = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unknown attribute, expected `scheme`, `style` or `serde`"))
+            }
+        })?;
+    }
+
+    let scheme = scheme.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "Missing `#[meme_id(scheme = \"...\")]` attribute",
+        )
+    })?;
+
+    let field = match &input.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Some(&fields.unnamed[0]),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let field = field.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "MemeId can be derived only for tuple struct with single field",
+        )
+    })?;
+    let field_ty = &field.ty;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "MemeId can't be derived for generic struct",
+        ));
+    }
+
+    let ident = &input.ident;
+    let name = ident.to_string();
+
+    let (phrase, hyphenated) = match style {
+        Style::Sentence => (quote!(phrase), false),
+        Style::Hyphenated => (quote!(::meme_id::Scheme::hyphenated(phrase)), true),
+    };
+
+    let mut tokens = quote! {
+        const _: () = ::core::assert!(
+            ::core::mem::size_of::<#field_ty>() * 8
+                <= ::meme_id::__private::bits(::meme_id::#scheme::encode) as usize,
+            "MemeId field type is wider than the scheme",
+        );
+
+        impl ::core::fmt::Display for #ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let phrase = ::meme_id::#scheme::encode(::core::convert::Into::into(self.0));
+                match ::meme_id::__private::Encoded::phrase(phrase) {
+                    Some(phrase) => ::core::fmt::Display::fmt(&#phrase, f),
+                    None => ::core::unreachable!("Field type fits into the scheme"),
+                }
+            }
+        }
+
+        impl ::core::fmt::Debug for #ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut tuple = f.debug_tuple(#name);
+                tuple.field(&self.0);
+                let phrase = ::meme_id::#scheme::encode(::core::convert::Into::into(self.0));
+                if let Some(phrase) = ::meme_id::__private::Encoded::phrase(phrase) {
+                    tuple.field(&::core::format_args!("{}", #phrase));
+                }
+                tuple.finish()
+            }
+        }
+
+        impl ::core::str::FromStr for #ident {
+            type Err = ::meme_id::ParseIdError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, ::meme_id::ParseIdError> {
+                let id = ::meme_id::#scheme::decode(s)?;
+                match ::core::convert::TryFrom::try_from(id) {
+                    Ok(id) => Ok(#ident(id)),
+                    Err(_) => Err(::meme_id::ParseIdError::Overflow),
+                }
+            }
+        }
+    };
+
+    if serde {
+        tokens.extend(quote! {
+            impl ::meme_id::__private::serde::Serialize for #ident {
+                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+                where
+                    S: ::meme_id::__private::serde::Serializer,
+                {
+                    let phrase = ::meme_id::#scheme::encode(::core::convert::Into::into(self.0));
+                    ::meme_id::__private::serialize_phrase(phrase, #hyphenated, serializer)
+                }
+            }
+
+            impl<'de> ::meme_id::__private::serde::Deserialize<'de> for #ident {
+                fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+                where
+                    D: ::meme_id::__private::serde::Deserializer<'de>,
+                {
+                    ::meme_id::__private::deserialize_phrase(deserializer, |s| {
+                        let id = ::meme_id::#scheme::decode(s)?;
+                        match ::core::convert::TryFrom::try_from(id) {
+                            Ok(id) => Ok(#ident(id)),
+                            Err(_) => Err(::meme_id::Error::Overflow),
+                        }
+                    })
+                }
+            }
+        });
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,86 @@
+//!
+//! Line by line conversion of IDs and phrases for `--batch` mode of binaries.
+//!
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+};
+
+/// Reads lines from the file or stdin and writes converted lines to stdout.
+/// Blank lines are copied as is.
+/// For each line that fails to convert, reports error to stderr
+/// and writes an empty line, so output lines match input lines.
+///
+/// Exits with non-zero status if any line failed.
+pub fn run(input: Option<&str>, mut convert: impl FnMut(&str) -> Result<String, String>) {
+    let reader = open(input);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let mut lines = 0;
+    let mut failed = 0;
+
+    for (idx, line) in reader.split(b'\n').enumerate() {
+        let line = match line {
+            Err(err) => {
+                flush(&mut out);
+                eprintln!("Failed to read input. {:#}", err);
+                std::process::exit(1);
+            }
+            Ok(line) => line,
+        };
+        lines += 1;
+
+        let result = match std::str::from_utf8(&line) {
+            Err(_) => Err("Line is not valid UTF-8".to_owned()),
+            Ok(line) if line.trim().is_empty() => Ok(String::new()),
+            Ok(line) => convert(line.trim()),
+        };
+
+        let output = match result {
+            Ok(output) => output,
+            Err(err) => {
+                eprintln!("line {}: {}", idx + 1, err);
+                failed += 1;
+                String::new()
+            }
+        };
+
+        if writeln!(out, "{}", output).is_err() {
+            // Output is closed, nobody reads the rest.
+            std::process::exit(1);
+        }
+    }
+
+    flush(&mut out);
+
+    if failed != 0 {
+        eprintln!("{} of {} lines failed", failed, lines);
+        std::process::exit(1);
+    }
+}
+
+/// Flushes converted lines to stdout.
+/// Exits if the output is closed.
+pub fn flush(out: &mut impl Write) {
+    if out.flush().is_err() {
+        std::process::exit(1);
+    }
+}
+
+/// Opens the file or stdin for reading.
+/// Exits if the file can't be opened.
+pub fn open(input: Option<&str>) -> Box<dyn BufRead> {
+    match input {
+        None => Box::new(io::stdin().lock()),
+        Some(path) => match File::open(path) {
+            Err(err) => {
+                eprintln!("Failed to open '{}'. {:#}", path, err);
+                std::process::exit(1);
+            }
+            Ok(file) => Box::new(BufReader::new(file)),
+        },
+    }
+}
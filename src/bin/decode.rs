@@ -1,73 +1,191 @@
 use std::env::args;
 
-use meme_id::{adjective_noun, complex_phrase, phrase, punk, simple_phrase, Error};
+mod batch;
+mod filter;
+
+use meme_id::{detect_and_decode, DetectError, Error, SchemeKind};
+
+const USAGE: &str = "Usage: meme-decode [--scheme <name>] [--format dec|hex|uuid] <phrase>...
+       meme-decode [options] --batch [--input <file>]
+       meme-decode [options] --filter [--field <name>]... [--replace] [--input <file>]
+
+Scheme is detected from number and classes of words, unless --scheme is given:
+var_phrase, preposition_pronoun, adjective_noun, adjective_noun_verb, simple_phrase,
+compound_phrase, phrase, punk, double_phrase or complex_phrase.
+
+--format prints numbers as decimal (default), 0x-prefixed hexadecimal
+or UUIDs in canonical form, like 67e55044-10b1-426f-9247-bb680e5fe0c8.
+
+--batch reads phrases from stdin, or from the file given with --input, one per line,
+and writes numbers line by line. Errors are reported to stderr with line numbers.
+
+--filter copies text, like application logs, from stdin or the file given with --input
+and appends numbers in parentheses after hyphenated phrases found in it.
+--replace puts numbers in place of phrases instead.
+--field limits conversion to values of named fields, like 'user=fluffy-cat'
+or '\"user\": \"Fluffy cat\"', and may be repeated.";
+
+#[derive(Clone, Copy)]
+enum Format {
+    Dec,
+    Hex,
+    Uuid,
+}
 
 fn main() {
-    let mut args = args();
-    let arg = match args.nth(1) {
-        None => {
-            eprintln!("Expected one numeric argument");
-            std::process::exit(1);
-        }
-        Some(arg) => arg,
-    };
+    let mut scheme = None;
+    let mut format = Format::Dec;
+    let mut batch = false;
+    let mut filter = false;
+    let mut fields = Vec::new();
+    let mut mode = filter::Mode::Append;
+    let mut input = None;
+    let mut phrases = Vec::new();
 
-    match adjective_noun::decode(&arg) {
-        Ok(num) => {
-            println!("{}", num);
-            return;
-        }
-        Err(Error::TrailingWords) => {}
-        Err(err) => {
-            eprintln!("Failed: {}", err);
-            std::process::exit(1);
+    let mut args = args().skip(1);
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "--scheme" => {
+                scheme = match args.next() {
+                    None => usage("Expected value after --scheme"),
+                    Some(name) => match SchemeKind::from_name(&name) {
+                        None => usage(&format!("Unknown scheme '{}'", name)),
+                        Some(scheme) => Some(scheme),
+                    },
+                }
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("dec") => Format::Dec,
+                    Some("hex") => Format::Hex,
+                    Some("uuid") => Format::Uuid,
+                    _ => usage("Expected --format dec, hex or uuid"),
+                }
+            }
+            "--batch" => batch = true,
+            "--filter" => filter = true,
+            "--field" => {
+                filter = true;
+                match args.next() {
+                    None => usage("Expected value after --field"),
+                    Some(value) => fields.push(value),
+                }
+            }
+            "--replace" => {
+                filter = true;
+                mode = filter::Mode::Replace;
+            }
+            "--input" => {
+                input = match args.next() {
+                    None => usage("Expected value after --input"),
+                    Some(value) => Some(value),
+                };
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if arg.starts_with("--") => usage(&format!("Unknown option '{}'", arg)),
+            _ => phrases.push(arg),
         }
     }
 
-    match simple_phrase::decode(&arg) {
-        Ok(num) => {
-            println!("{}", num);
-            return;
-        }
-        Err(Error::TrailingWords) => {}
-        Err(err) => {
-            eprintln!("Failed: {}", err);
-            std::process::exit(1);
-        }
+    if filter && batch {
+        usage("Options --filter and --batch can't be used together");
     }
 
-    match phrase::decode(&arg) {
-        Ok(num) => {
-            println!("{}", num);
-            return;
-        }
-        Err(Error::TrailingWords) => {}
-        Err(err) => {
-            eprintln!("Failed: {}", err);
-            std::process::exit(1);
+    if filter {
+        if !phrases.is_empty() {
+            usage("Unexpected phrase argument in filter mode");
         }
+
+        filter::run(input.as_deref(), &fields, mode, is_phrase_char, |value| {
+            decode(value, scheme)
+                .ok()
+                .map(|num| format_number(num, format))
+        });
+        return;
     }
 
-    match punk::decode(&arg) {
-        Ok(num) => {
-            println!("{}", num);
-            return;
+    if batch || input.is_some() {
+        if !phrases.is_empty() {
+            usage("Unexpected phrase argument in batch mode");
         }
-        Err(Error::TrailingWords) => {}
+
+        batch::run(input.as_deref(), |line| match decode(line, scheme) {
+            Ok(num) => Ok(format_number(num, format)),
+            Err(err) => match suggest(&err.error) {
+                None => Err(err.to_string()),
+                Some(suggestion) => Err(format!("{}. {}", err, suggestion)),
+            },
+        });
+        return;
+    }
+
+    if phrases.is_empty() {
+        usage("Expected phrase argument");
+    }
+
+    // Unquoted phrase comes in separate arguments.
+    let arg = phrases.join(" ");
+
+    match decode(&arg, scheme) {
+        Ok(num) => println!("{}", format_number(num, format)),
         Err(err) => {
             eprintln!("Failed: {}", err);
+            if let Some(suggestion) = suggest(&err.error) {
+                eprintln!("{}", suggestion);
+            }
             std::process::exit(1);
         }
     }
+}
 
-    match complex_phrase::decode(&arg) {
-        Ok(num) => {
-            println!("{}", num);
-            return;
-        }
-        Err(err) => {
-            eprintln!("Failed: {}", err);
-            std::process::exit(1);
+fn usage(msg: &str) -> ! {
+    eprintln!("{}\n\n{}", msg, USAGE);
+    std::process::exit(1);
+}
+
+fn format_number(num: u128, format: Format) -> String {
+    match format {
+        Format::Dec => num.to_string(),
+        Format::Hex => format!("{:#x}", num),
+        Format::Uuid => {
+            let hex = format!("{:032x}", num);
+            format!(
+                "{}-{}-{}-{}-{}",
+                &hex[..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..]
+            )
         }
     }
 }
+
+/// Checks if the character may be a part of hyphenated phrase in text.
+fn is_phrase_char(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '-'
+}
+
+/// Decodes phrase with the given scheme or detects the scheme.
+fn decode(s: &str, scheme: Option<SchemeKind>) -> Result<u128, DetectError<'_>> {
+    match scheme {
+        None => detect_and_decode(s).map(|(_, num)| num),
+        Some(scheme) => scheme
+            .decode(s)
+            .map_err(|error| DetectError { scheme, error }),
+    }
+}
+
+/// Suggests words in place of unrecognized one.
+fn suggest(err: &Error) -> Option<String> {
+    let mut suggestions = [""; 5];
+    let suggestions = err.suggestions(&mut suggestions);
+    if suggestions.is_empty() {
+        None
+    } else {
+        Some(format!("Did you mean: {}?", suggestions.join(", ")))
+    }
+}
@@ -0,0 +1,173 @@
+use std::{env::args, fmt::Write as _, fs};
+
+use meme_id::mapper::{try_eytzinger_layout, LayoutError};
+
+const USAGE: &str = "Usage: meme-dict [--check] <words.txt> [<dict.rs>]";
+
+fn main() {
+    let mut check = false;
+    let mut paths = Vec::new();
+
+    for arg in args().skip(1) {
+        match &*arg {
+            "--check" => check = true,
+            _ => paths.push(arg),
+        }
+    }
+
+    let (input, output) = match (&*paths, check) {
+        ([input], false) => (input, None),
+        ([input, output], _) => (input, Some(output)),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    let text = match fs::read_to_string(input) {
+        Err(err) => {
+            eprintln!("Failed to read '{}'. {:#}", input, err);
+            std::process::exit(1);
+        }
+        Ok(text) => text,
+    };
+
+    let mut words: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let mut failed = !lint(&words);
+
+    let mut array = vec![""; words.len()];
+    if let Err(err) = try_eytzinger_layout(&mut words, &mut array) {
+        // Invalid words are already reported by the lint.
+        if let LayoutError::NotPowerOfTwo { len } = err {
+            eprintln!("error: {}", err);
+            eprintln!(
+                "note: closest powers of two are {} and {}",
+                len.next_power_of_two() / 2,
+                len.next_power_of_two()
+            );
+        }
+        failed = true;
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+
+    let source = generate(&array);
+
+    match output {
+        None => print!("{}", source),
+        Some(output) if check => match fs::read_to_string(output) {
+            Ok(existing) if existing == source => {}
+            Ok(_) => {
+                eprintln!("'{}' is not generated from '{}'", output, input);
+                std::process::exit(1);
+            }
+            Err(err) => {
+                eprintln!("Failed to read '{}'. {:#}", output, err);
+                std::process::exit(1);
+            }
+        },
+        Some(output) => {
+            if let Err(err) = fs::write(output, source) {
+                eprintln!("Failed to write '{}'. {:#}", output, err);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Reports every invalid word and words that are valid but may cause problems.
+/// Returns `false` if any word is invalid.
+fn lint(words: &[&str]) -> bool {
+    let mut valid = true;
+
+    let mut sorted = words.to_vec();
+    sorted.sort_by_cached_key(|word| word.to_ascii_lowercase());
+
+    // Words that are prefixes of the current word.
+    // Sorted order puts every word right after the words it starts with.
+    let mut prefixes: Vec<&str> = Vec::new();
+
+    for &word in &sorted {
+        if word.is_empty() || !word.bytes().all(|b| b.is_ascii_alphabetic()) {
+            eprintln!("error: {}", LayoutError::NotAlphabetic { word });
+            valid = false;
+            continue;
+        }
+
+        while let Some(prefix) = prefixes.last() {
+            if word.len() >= prefix.len() && word[..prefix.len()].eq_ignore_ascii_case(prefix) {
+                break;
+            }
+            prefixes.pop();
+        }
+
+        if prefixes
+            .last()
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(word))
+        {
+            eprintln!("error: {}", LayoutError::Duplicate { word });
+            valid = false;
+            continue;
+        }
+
+        for prefix in &prefixes {
+            eprintln!("warning: '{}' is a prefix of '{}'", prefix, word);
+        }
+        prefixes.push(word);
+    }
+
+    if words.is_empty() {
+        return valid;
+    }
+
+    let count = words.len() as f64;
+    let mean = words.iter().map(|word| word.len() as f64).sum::<f64>() / count;
+    let variance = words
+        .iter()
+        .map(|word| (word.len() as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    let deviation = variance.sqrt();
+
+    for word in words {
+        if (word.len() as f64 - mean).abs() > 3.0 * deviation {
+            eprintln!(
+                "warning: '{}' length {} is far from average {:.1}",
+                word,
+                word.len(),
+                mean
+            );
+        }
+    }
+
+    valid
+}
+
+/// Generates source of dictionary module.
+fn generate(array: &[&str]) -> String {
+    let mut source = String::new();
+
+    writeln!(source).unwrap();
+    writeln!(source).unwrap();
+    writeln!(source, "pub const WORD_ARRAY: [&str; {}] = [", array.len()).unwrap();
+    for word in array {
+        writeln!(source, "    {:?},", word).unwrap();
+    }
+    writeln!(source, "];").unwrap();
+    writeln!(source).unwrap();
+    writeln!(source).unwrap();
+    writeln!(
+        source,
+        "pub const WORD_MAPPER: crate::mapper::WordMapper<'static> = crate::mapper::WordMapper::new(&WORD_ARRAY);"
+    )
+    .unwrap();
+
+    source
+}
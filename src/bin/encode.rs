@@ -1,33 +1,284 @@
-use std::env::args;
+use std::{convert::TryFrom, env::args};
 
-use meme_id::{adjective_noun, complex_phrase, phrase, simple_phrase};
+mod batch;
+mod filter;
+
+use meme_id::{
+    var_phrase, AdjectiveNoun, AdjectiveNounVerb, ComplexPhrase, CompoundPhrase, DoublePhrase,
+    Hyphenated, Phrase, PrepositionPronoun, Punk, Scheme, SimplePhrase,
+};
+
+const USAGE: &str = "Usage: meme-encode [--scheme <name>] [--bits <n>] [--style sentence|hyphenated] <number>
+       meme-encode [options] --batch [--input <file>]
+       meme-encode [options] --filter [--field <name>]... [--replace] [--input <file>]
+
+Schemes:
+    var_phrase           up to 128 bits, as many words as the number needs (default)
+    preposition_pronoun  8 bits
+    adjective_noun       16 bits
+    adjective_noun_verb  24 bits
+    simple_phrase        32 bits
+    compound_phrase      48 bits
+    phrase               64 bits
+    punk                 64 bits
+    double_phrase        96 bits
+    complex_phrase       128 bits
+
+--bits sets width of IDs. Numbers wider than that are rejected.
+Smaller numbers are zero-extended, so var_phrase emits the same number of words for all IDs of that width.
+
+Numbers are decimal, 0x-prefixed hexadecimal, 0o-prefixed octal, 0b-prefixed binary
+or UUIDs in canonical form, like 67e55044-10b1-426f-9247-bb680e5fe0c8.
+
+--batch reads numbers from stdin, or from the file given with --input, one per line,
+and writes phrases line by line. Errors are reported to stderr with line numbers.
+
+--filter copies text, like application logs, from stdin or the file given with --input
+and appends phrases in parentheses after numbers and UUIDs found in it.
+--replace puts phrases in place of numbers instead.
+--field limits conversion to values of named fields, like 'user=42' or '\"user\": \"42\"',
+and may be repeated. Phrases are hyphenated in this mode, unless --style is given.";
+
+#[derive(Clone, Copy)]
+enum Style {
+    Sentence,
+    Hyphenated,
+}
 
 fn main() {
-    let mut args = args();
-    let arg = match args.nth(1) {
-        None => {
-            eprintln!("Expected one numeric argument");
-            std::process::exit(1);
+    let mut scheme = None;
+    let mut bits = None;
+    let mut style = None;
+    let mut batch = false;
+    let mut filter = false;
+    let mut fields = Vec::new();
+    let mut mode = filter::Mode::Append;
+    let mut input = None;
+    let mut numbers = Vec::new();
+
+    let mut args = args().skip(1);
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "--scheme" => scheme = Some(value(&mut args, "--scheme")),
+            "--bits" => {
+                let arg = value(&mut args, "--bits");
+                match arg.parse::<u32>() {
+                    Ok(n) if (1..=128).contains(&n) => bits = Some(n),
+                    _ => usage(&format!("Expected --bits from 1 to 128, got '{}'", arg)),
+                }
+            }
+            "--style" => {
+                style = match &*value(&mut args, "--style") {
+                    "sentence" => Some(Style::Sentence),
+                    "hyphenated" => Some(Style::Hyphenated),
+                    arg => usage(&format!(
+                        "Expected --style sentence or hyphenated, got '{}'",
+                        arg
+                    )),
+                }
+            }
+            "--batch" => batch = true,
+            "--filter" => filter = true,
+            "--field" => {
+                filter = true;
+                fields.push(value(&mut args, "--field"));
+            }
+            "--replace" => {
+                filter = true;
+                mode = filter::Mode::Replace;
+            }
+            "--input" => input = Some(value(&mut args, "--input")),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if arg.starts_with("--") => usage(&format!("Unknown option '{}'", arg)),
+            _ => numbers.push(arg),
         }
-        Some(arg) => arg,
+    }
+
+    let scheme = scheme.as_deref().unwrap_or("var_phrase");
+
+    if filter && batch {
+        usage("Options --filter and --batch can't be used together");
+    }
+
+    // Phrases in text should stay single words.
+    let style = match style {
+        Some(style) => style,
+        None if filter => Style::Hyphenated,
+        None => Style::Sentence,
     };
 
-    let num: u128 = match arg.parse() {
+    // Reports unknown scheme and unsupported width once, rather than for each number.
+    if let Err(err) = encode(scheme, 0, bits, style) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+
+    if filter {
+        if !numbers.is_empty() {
+            usage("Unexpected numeric argument in filter mode");
+        }
+
+        filter::run(input.as_deref(), &fields, mode, is_number_char, |value| {
+            let num = parse_number(value).ok()?;
+            encode(scheme, num, bits, style).ok()
+        });
+        return;
+    }
+
+    if batch || input.is_some() {
+        if !numbers.is_empty() {
+            usage("Unexpected numeric argument in batch mode");
+        }
+
+        batch::run(input.as_deref(), |line| {
+            let num = parse_number(line)?;
+            encode(scheme, num, bits, style)
+        });
+        return;
+    }
+
+    let arg = match &*numbers {
+        [arg] => arg,
+        _ => usage("Expected one numeric argument"),
+    };
+
+    let num = match parse_number(arg) {
         Err(err) => {
-            eprintln!("Expected one numeric argument. {:#}", err);
+            eprintln!("{}", err);
             std::process::exit(1);
         }
         Ok(num) => num,
     };
 
-    if num <= u16::MAX as u128 {
-        println!("{}", adjective_noun::encode(num as u16).to_string());
-    } else if num <= u32::MAX as u128 {
-        println!("{}", simple_phrase::encode(num as u32).to_string());
-    } else if num <= u64::MAX as u128 {
-        println!("{}", phrase::encode(num as u64).to_string());
-        // println!("{}", punk::encode(num as u64).to_string());
-    } else {
-        println!("{}", complex_phrase::encode(num).to_string());
+    match encode(scheme, num, bits, style) {
+        Ok(phrase) => println!("{}", phrase),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Takes value of the option from the arguments.
+fn value(args: &mut impl Iterator<Item = String>, option: &str) -> String {
+    match args.next() {
+        None => usage(&format!("Expected value after {}", option)),
+        Some(value) => value,
+    }
+}
+
+fn usage(msg: &str) -> ! {
+    eprintln!("{}\n\n{}", msg, USAGE);
+    std::process::exit(1);
+}
+
+/// Parses number in one of supported formats.
+fn parse_number(s: &str) -> Result<u128, String> {
+    if let Some(num) = parse_uuid(s) {
+        return Ok(num);
+    }
+
+    let (digits, radix) = match s.get(..2) {
+        Some("0x") | Some("0X") => (&s[2..], 16),
+        Some("0o") | Some("0O") => (&s[2..], 8),
+        Some("0b") | Some("0B") => (&s[2..], 2),
+        _ => (s, 10),
+    };
+
+    u128::from_str_radix(digits, radix).map_err(|err| format!("Expected number. {:#}", err))
+}
+
+/// Checks if the character may be a part of number in text.
+/// Numbers adjacent to letters, dots, colons and hyphens, like in '10.0.0.1' or '12:30',
+/// are not IDs and fail to parse as a whole.
+fn is_number_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | ':' | '_')
+}
+
+/// Parses UUID in canonical hyphenated form as a big-endian number.
+fn parse_uuid(s: &str) -> Option<u128> {
+    const HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+    let bytes = s.as_bytes();
+    if bytes.len() != 36 || HYPHENS.iter().any(|&idx| bytes[idx] != b'-') {
+        return None;
+    }
+
+    let mut num = 0;
+    for (idx, &byte) in bytes.iter().enumerate() {
+        if !HYPHENS.contains(&idx) {
+            num = num << 4 | u128::from(char::from(byte).to_digit(16)?);
+        }
+    }
+    Some(num)
+}
+
+/// Encodes number with the named scheme.
+/// Number must fit into `bits` when specified.
+fn encode(scheme: &str, num: u128, bits: Option<u32>, style: Style) -> Result<String, String> {
+    if let Some(bits) = bits {
+        if bits < 128 && num >> bits != 0 {
+            return Err(format!("Number {} doesn't fit into {} bits", num, bits));
+        }
+    }
+
+    match scheme {
+        "var_phrase" => {
+            let phrase = var_phrase::encode_zero_extended(num, bits.unwrap_or(0));
+            Ok(render(phrase, style))
+        }
+        "preposition_pronoun" => encode_fixed::<PrepositionPronoun>(scheme, num, bits, style),
+        "adjective_noun" => encode_fixed::<AdjectiveNoun>(scheme, num, bits, style),
+        "adjective_noun_verb" => encode_fixed::<AdjectiveNounVerb>(scheme, num, bits, style),
+        "simple_phrase" => encode_fixed::<SimplePhrase>(scheme, num, bits, style),
+        "compound_phrase" => encode_fixed::<CompoundPhrase>(scheme, num, bits, style),
+        "phrase" => encode_fixed::<Phrase>(scheme, num, bits, style),
+        "punk" => encode_fixed::<Punk>(scheme, num, bits, style),
+        "double_phrase" => encode_fixed::<DoublePhrase>(scheme, num, bits, style),
+        "complex_phrase" => encode_fixed::<ComplexPhrase>(scheme, num, bits, style),
+        _ => Err(format!("Unknown scheme '{}'\n\n{}", scheme, USAGE)),
+    }
+}
+
+/// Encodes number with fixed width scheme.
+fn encode_fixed<S: Scheme>(
+    name: &str,
+    num: u128,
+    bits: Option<u32>,
+    style: Style,
+) -> Result<String, String> {
+    if let Some(bits) = bits {
+        if bits > S::BITS {
+            return Err(format!(
+                "Scheme {} encodes up to {} bits, not {}",
+                name,
+                S::BITS,
+                bits
+            ));
+        }
+    }
+
+    if S::BITS < 128 && num >> S::BITS != 0 {
+        return Err(format!(
+            "Number {} doesn't fit into {} bits of scheme {}",
+            num,
+            S::BITS,
+            name
+        ));
+    }
+
+    match S::Id::try_from(num) {
+        Ok(id) => Ok(render(S::encode(id), style)),
+        Err(_) => unreachable!("Scheme ID holds all of its bits"),
+    }
+}
+
+fn render<S: Scheme>(phrase: S, style: Style) -> String {
+    match style {
+        Style::Sentence => phrase.to_string(),
+        Style::Hyphenated => Hyphenated(phrase).to_string(),
     }
 }
@@ -0,0 +1,156 @@
+//!
+//! Conversion of IDs and phrases embedded in text for `--filter` mode of binaries.
+//!
+
+use std::{
+    io::{self, BufRead, BufWriter, Write},
+    ops::Range,
+};
+
+/// How converted values are written.
+#[derive(Clone, Copy)]
+pub enum Mode {
+    /// Converted value follows the original one in parentheses.
+    Append,
+    /// Converted value takes place of the original one.
+    Replace,
+}
+
+/// Reads lines from the file or stdin and writes them to stdout
+/// with values converted by `convert`.
+///
+/// Without `fields`, values are maximal runs of characters accepted by `is_token`.
+/// With `fields`, values are those following `name=` or `name:`,
+/// possibly quoted as in JSON, like `"name": "value"`.
+///
+/// Values that fail to convert and lines that are not valid UTF-8 are copied as is.
+pub fn run(
+    input: Option<&str>,
+    fields: &[String],
+    mode: Mode,
+    is_token: fn(char) -> bool,
+    mut convert: impl FnMut(&str) -> Option<String>,
+) {
+    let reader = crate::batch::open(input);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let mut output = String::new();
+
+    for line in reader.split(b'\n') {
+        let line = match line {
+            Err(err) => {
+                crate::batch::flush(&mut out);
+                eprintln!("Failed to read input. {:#}", err);
+                std::process::exit(1);
+            }
+            Ok(line) => line,
+        };
+
+        let result = match std::str::from_utf8(&line) {
+            Err(_) => out.write_all(&line),
+            Ok(line) => {
+                output.clear();
+
+                let mut copied = 0;
+                for span in values(line, fields, is_token) {
+                    let value = &line[span.clone()];
+                    if let Some(converted) = convert(value) {
+                        output.push_str(&line[copied..span.start]);
+                        match mode {
+                            Mode::Append => {
+                                output.push_str(value);
+                                output.push_str(" (");
+                                output.push_str(&converted);
+                                output.push(')');
+                            }
+                            Mode::Replace => output.push_str(&converted),
+                        }
+                        copied = span.end;
+                    }
+                }
+                output.push_str(&line[copied..]);
+
+                out.write_all(output.as_bytes())
+            }
+        };
+
+        if result.and_then(|()| out.write_all(b"\n")).is_err() {
+            // Output is closed, nobody reads the rest.
+            std::process::exit(1);
+        }
+    }
+
+    crate::batch::flush(&mut out);
+}
+
+/// Finds values in the line, in order of appearance.
+fn values(line: &str, fields: &[String], is_token: fn(char) -> bool) -> Vec<Range<usize>> {
+    if fields.is_empty() {
+        let mut values = Vec::new();
+        let mut rest = 0;
+        while let Some(offset) = line[rest..].find(is_token) {
+            let start = rest + offset;
+            let span = token(line, start, is_token);
+            rest = line[start..]
+                .find(|c| !is_token(c))
+                .map_or(line.len(), |len| start + len);
+            if !span.is_empty() {
+                values.push(span);
+            }
+        }
+        return values;
+    }
+
+    let mut values: Vec<Range<usize>> = fields
+        .iter()
+        .flat_map(|field| line.match_indices(&**field))
+        .filter_map(|(start, field)| field_value(line, start, field, is_token))
+        .collect();
+
+    values.sort_by_key(|span| span.start);
+    values.dedup_by(|next, prev| next.start < prev.end);
+    values
+}
+
+/// Returns span of the value of the field whose name is at `start` in the line.
+fn field_value(
+    line: &str,
+    start: usize,
+    name: &str,
+    is_token: fn(char) -> bool,
+) -> Option<Range<usize>> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    // Name must not be a part of another word.
+    if line[..start].chars().next_back().is_some_and(is_word) {
+        return None;
+    }
+
+    let rest = &line[start + name.len()..];
+    let rest = rest.strip_prefix(&['"', '\''][..]).unwrap_or(rest);
+    let rest = rest.trim_start().strip_prefix(&['=', ':'][..])?;
+    let rest = rest.trim_start();
+    let offset = line.len() - rest.len();
+
+    match rest.chars().next()? {
+        quote @ '"' | quote @ '\'' => {
+            let len = rest[1..].find(quote)?;
+            Some(offset + 1..offset + 1 + len)
+        }
+        c if is_token(c) => Some(token(line, offset, is_token)),
+        _ => None,
+    }
+}
+
+/// Returns span of the token starting at `start`.
+/// Trailing punctuation, like full stop after a number, is not a part of the token.
+fn token(line: &str, start: usize, is_token: fn(char) -> bool) -> Range<usize> {
+    let rest = &line[start..];
+    let len = rest.find(|c| !is_token(c)).unwrap_or(rest.len());
+    let len = rest[..len]
+        .trim_end_matches(|c: char| !c.is_alphanumeric())
+        .len();
+    start..start + len
+}
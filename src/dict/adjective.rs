@@ -1,6 +1,6 @@
 
 
-pub const WORD_ARRAY: [&'static str; 1024] = [
+pub const WORD_ARRAY: [&str; 1024] = [
     "masterly",
     "exact",
     "showy",
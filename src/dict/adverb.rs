@@ -1,6 +1,6 @@
 
 
-pub const WORD_ARRAY: [&'static str; 512] = [
+pub const WORD_ARRAY: [&str; 512] = [
     "learnedly",
     "engagingly",
     "safely",
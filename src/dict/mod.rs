@@ -1,3 +1,42 @@
+//!
+//! Word classes used by the schemes.
+//!
+//! Custom dictionaries can be plugged into the schemes by implementing
+//! [`Mapper`] for own word classes and [`Dictionary`] to group them.
+//!
+//! ```
+//! use meme_id::{
+//!     dict::{self, Dictionary, Mapper, WordClass},
+//!     mapper::eytzinger_layout,
+//!     punk, WordMapper,
+//! };
+//!
+//! static PRONOUNS: [&str; 4] = eytzinger_layout(["him", "her", "them", "us"]);
+//!
+//! enum Pronoun {}
+//!
+//! impl Mapper for Pronoun {
+//!     const CLASS: WordClass = WordClass::ObjectPronoun;
+//!     const WORD_MAPPER: WordMapper<'static> = WordMapper::new(&PRONOUNS);
+//! }
+//!
+//! enum Custom {}
+//!
+//! impl Dictionary for Custom {
+//!     type Adjective = dict::Adjective;
+//!     type Noun = dict::Noun;
+//!     type VerbSingular = dict::Verb<dict::Singular>;
+//!     type VerbPlural = dict::Verb<dict::Plural>;
+//!     type Adverb = dict::Adverb;
+//!     type Preposition = dict::Preposition;
+//!     type ObjectPronoun = Pronoun;
+//! }
+//!
+//! let phrase = punk::encode_with::<Custom>(42).to_string();
+//! assert_eq!(punk::decode_with::<Custom>(&phrase), Ok(42));
+//! ```
+//!
+
 mod adjective;
 mod adverb;
 mod noun;
@@ -6,49 +45,147 @@ mod preposition;
 mod verb_plural;
 mod verb_singular;
 
+use core::fmt;
+
+use crate::{mapper::WordMapper, matcher::Matcher, Error};
+
 pub enum Singular {}
 
 pub enum Plural {}
 
-pub trait Mapper {
-    fn encode_word(bits: u128) -> (&'static str, u128);
-    fn encode_words<const N: usize>(bits: u128) -> ([&'static str; N], u128);
-    fn decode_word(word: &str, bits: u128) -> Option<u128>;
-    fn decode_words<const N: usize>(words: [&str; N], bits: u128) -> Result<u128, usize>;
+/// Grammatical class of words in a phrase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WordClass {
+    Adjective,
+    Noun,
+    VerbSingular,
+    VerbPlural,
+    Adverb,
+    Preposition,
+    ObjectPronoun,
 }
 
-pub enum Adjective {}
+impl fmt::Display for WordClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordClass::Adjective => f.write_str("adjective"),
+            WordClass::Noun => f.write_str("noun"),
+            WordClass::VerbSingular | WordClass::VerbPlural => f.write_str("verb"),
+            WordClass::Adverb => f.write_str("adverb"),
+            WordClass::Preposition => f.write_str("preposition"),
+            WordClass::ObjectPronoun => f.write_str("pronoun"),
+        }
+    }
+}
+
+/// Class of words that maps bits to words and back.
+pub trait Mapper {
+    /// Grammatical class of words.
+    const CLASS: WordClass;
+
+    /// Mapper with words of this class.
+    const WORD_MAPPER: WordMapper<'static>;
 
-impl Mapper for Adjective {
     fn encode_word(bits: u128) -> (&'static str, u128) {
-        adjective::WORD_MAPPER.encode_word(bits)
+        Self::WORD_MAPPER.encode_word(bits)
     }
     fn encode_words<const N: usize>(bits: u128) -> ([&'static str; N], u128) {
-        adjective::WORD_MAPPER.encode_words_norepeat(bits)
+        Self::WORD_MAPPER.encode_words_norepeat(bits)
+    }
+    fn decode_word<'a>(
+        word: &'a str,
+        bits: u128,
+        matcher: &mut impl Matcher,
+    ) -> Result<u128, Error<'a>> {
+        let idx = matcher
+            .find(&Self::WORD_MAPPER, word)
+            .map_err(|mismatch| mismatch.into_error(word, Self::CLASS))?;
+        Ok(Self::WORD_MAPPER.decode_index(idx, bits))
+    }
+    fn decode_words<'a, const N: usize>(
+        words: [&'a str; N],
+        bits: u128,
+        matcher: &mut impl Matcher,
+    ) -> Result<u128, Error<'a>> {
+        let mut indices = [0; N];
+        for (idx, word) in indices.iter_mut().zip(words.iter()) {
+            *idx = matcher
+                .find(&Self::WORD_MAPPER, word)
+                .map_err(|mismatch| mismatch.into_error(word, Self::CLASS))?;
+        }
+        Ok(Self::decode_indices(indices, bits))
+    }
+    fn decode_indices<const N: usize>(indices: [usize; N], bits: u128) -> u128 {
+        Self::WORD_MAPPER.decode_indices_norepeat(indices, bits)
     }
-    fn decode_word(word: &str, bits: u128) -> Option<u128> {
-        adjective::WORD_MAPPER.decode_word(word, bits)
-    }
-    fn decode_words<const N: usize>(words: [&str; N], bits: u128) -> Result<u128, usize> {
-        adjective::WORD_MAPPER.decode_words_norepeat(words, bits)
+}
+
+/// Set of word classes used by the schemes.
+///
+/// Each class must contain the same number of words
+/// as the class from [`Standard`] dictionary it replaces,
+/// otherwise schemes won't be able to encode all bits.
+pub trait Dictionary {
+    /// 1024 adjectives.
+    type Adjective: Mapper;
+
+    /// 512 nouns.
+    type Noun: Mapper;
+
+    /// 512 verbs used with singular subject.
+    type VerbSingular: Mapper;
+
+    /// 512 verbs used with plural subject.
+    type VerbPlural: Mapper;
+
+    /// 512 adverbs.
+    type Adverb: Mapper;
+
+    /// 64 prepositions.
+    type Preposition: Mapper;
+
+    /// 4 object pronouns.
+    type ObjectPronoun: Mapper;
+
+    /// Returns mapper for the words of the class.
+    fn word_mapper(class: WordClass) -> WordMapper<'static> {
+        match class {
+            WordClass::Adjective => Self::Adjective::WORD_MAPPER,
+            WordClass::Noun => Self::Noun::WORD_MAPPER,
+            WordClass::VerbSingular => Self::VerbSingular::WORD_MAPPER,
+            WordClass::VerbPlural => Self::VerbPlural::WORD_MAPPER,
+            WordClass::Adverb => Self::Adverb::WORD_MAPPER,
+            WordClass::Preposition => Self::Preposition::WORD_MAPPER,
+            WordClass::ObjectPronoun => Self::ObjectPronoun::WORD_MAPPER,
+        }
     }
 }
 
+/// Dictionary built into the crate.
+pub enum Standard {}
+
+impl Dictionary for Standard {
+    type Adjective = Adjective;
+    type Noun = Noun;
+    type VerbSingular = Verb<Singular>;
+    type VerbPlural = Verb<Plural>;
+    type Adverb = Adverb;
+    type Preposition = Preposition;
+    type ObjectPronoun = ObjectPronoun;
+}
+
+pub enum Adjective {}
+
+impl Mapper for Adjective {
+    const CLASS: WordClass = WordClass::Adjective;
+    const WORD_MAPPER: WordMapper<'static> = adjective::WORD_MAPPER;
+}
+
 pub enum Noun {}
 
 impl Mapper for Noun {
-    fn encode_word(bits: u128) -> (&'static str, u128) {
-        noun::WORD_MAPPER.encode_word(bits)
-    }
-    fn encode_words<const N: usize>(bits: u128) -> ([&'static str; N], u128) {
-        noun::WORD_MAPPER.encode_words_norepeat(bits)
-    }
-    fn decode_word(word: &str, bits: u128) -> Option<u128> {
-        noun::WORD_MAPPER.decode_word(word, bits)
-    }
-    fn decode_words<const N: usize>(words: [&str; N], bits: u128) -> Result<u128, usize> {
-        noun::WORD_MAPPER.decode_words_norepeat(words, bits)
-    }
+    const CLASS: WordClass = WordClass::Noun;
+    const WORD_MAPPER: WordMapper<'static> = noun::WORD_MAPPER;
 }
 
 pub enum Verb<T> {
@@ -56,82 +193,39 @@ pub enum Verb<T> {
 }
 
 impl Mapper for Verb<Singular> {
-    fn encode_word(bits: u128) -> (&'static str, u128) {
-        verb_singular::WORD_MAPPER.encode_word(bits)
-    }
-    fn encode_words<const N: usize>(bits: u128) -> ([&'static str; N], u128) {
-        verb_singular::WORD_MAPPER.encode_words_norepeat(bits)
-    }
-    fn decode_word(word: &str, bits: u128) -> Option<u128> {
-        verb_singular::WORD_MAPPER.decode_word(word, bits)
-    }
-    fn decode_words<const N: usize>(words: [&str; N], bits: u128) -> Result<u128, usize> {
-        verb_singular::WORD_MAPPER.decode_words_norepeat(words, bits)
-    }
+    const CLASS: WordClass = WordClass::VerbSingular;
+    const WORD_MAPPER: WordMapper<'static> = verb_singular::WORD_MAPPER;
 }
 
 impl Mapper for Verb<Plural> {
-    fn encode_word(bits: u128) -> (&'static str, u128) {
-        verb_plural::WORD_MAPPER.encode_word(bits)
-    }
-    fn encode_words<const N: usize>(bits: u128) -> ([&'static str; N], u128) {
-        verb_plural::WORD_MAPPER.encode_words_norepeat(bits)
-    }
-    fn decode_word(word: &str, bits: u128) -> Option<u128> {
-        verb_plural::WORD_MAPPER.decode_word(word, bits)
-    }
-    fn decode_words<const N: usize>(words: [&str; N], bits: u128) -> Result<u128, usize> {
-        verb_plural::WORD_MAPPER.decode_words_norepeat(words, bits)
-    }
+    const CLASS: WordClass = WordClass::VerbPlural;
+    const WORD_MAPPER: WordMapper<'static> = verb_plural::WORD_MAPPER;
 }
 
 pub enum Adverb {}
 
 impl Mapper for Adverb {
-    fn encode_word(bits: u128) -> (&'static str, u128) {
-        adverb::WORD_MAPPER.encode_word(bits)
-    }
-    fn encode_words<const N: usize>(bits: u128) -> ([&'static str; N], u128) {
-        adverb::WORD_MAPPER.encode_words_norepeat(bits)
-    }
-    fn decode_word(word: &str, bits: u128) -> Option<u128> {
-        adverb::WORD_MAPPER.decode_word(word, bits)
-    }
-    fn decode_words<const N: usize>(words: [&str; N], bits: u128) -> Result<u128, usize> {
-        adverb::WORD_MAPPER.decode_words_norepeat(words, bits)
-    }
+    const CLASS: WordClass = WordClass::Adverb;
+    const WORD_MAPPER: WordMapper<'static> = adverb::WORD_MAPPER;
 }
 
 pub enum Preposition {}
 
 impl Mapper for Preposition {
-    fn encode_word(bits: u128) -> (&'static str, u128) {
-        preposition::WORD_MAPPER.encode_word(bits)
-    }
-    fn encode_words<const N: usize>(bits: u128) -> ([&'static str; N], u128) {
-        preposition::WORD_MAPPER.encode_words_norepeat(bits)
-    }
-    fn decode_word(word: &str, bits: u128) -> Option<u128> {
-        preposition::WORD_MAPPER.decode_word(word, bits)
-    }
-    fn decode_words<const N: usize>(words: [&str; N], bits: u128) -> Result<u128, usize> {
-        preposition::WORD_MAPPER.decode_words_norepeat(words, bits)
-    }
+    const CLASS: WordClass = WordClass::Preposition;
+    const WORD_MAPPER: WordMapper<'static> = preposition::WORD_MAPPER;
 }
 
 pub enum ObjectPronoun {}
 
 impl Mapper for ObjectPronoun {
-    fn encode_word(bits: u128) -> (&'static str, u128) {
-        object_pronoun::WORD_MAPPER.encode_word(bits)
-    }
+    const CLASS: WordClass = WordClass::ObjectPronoun;
+    const WORD_MAPPER: WordMapper<'static> = object_pronoun::WORD_MAPPER;
+
     fn encode_words<const N: usize>(bits: u128) -> ([&'static str; N], u128) {
-        object_pronoun::WORD_MAPPER.encode_words(bits)
-    }
-    fn decode_word(word: &str, bits: u128) -> Option<u128> {
-        object_pronoun::WORD_MAPPER.decode_word(word, bits)
+        Self::WORD_MAPPER.encode_words(bits)
     }
-    fn decode_words<const N: usize>(words: [&str; N], bits: u128) -> Result<u128, usize> {
-        object_pronoun::WORD_MAPPER.decode_words(words, bits)
+    fn decode_indices<const N: usize>(indices: [usize; N], bits: u128) -> u128 {
+        Self::WORD_MAPPER.decode_indices(indices, bits)
     }
 }
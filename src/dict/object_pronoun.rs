@@ -1,6 +1,6 @@
 
 
-pub const WORD_ARRAY: [&'static str; 4] = [
+pub const WORD_ARRAY: [&str; 4] = [
     "us",
     "me",
     "you",
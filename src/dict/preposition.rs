@@ -1,6 +1,6 @@
 
 
-pub const WORD_ARRAY: [&'static str; 64] = [
+pub const WORD_ARRAY: [&str; 64] = [
     "minus",
     "between",
     "through",
@@ -0,0 +1,307 @@
+use core::{cmp, fmt, hash, marker::PhantomData, str::FromStr};
+
+use crate::{dict::WordClass, Error, Hyphenated, Scheme};
+
+/// Typed ID that is displayed and parsed as a phrase of the scheme `S`.
+///
+/// `T` is a tag type that distinguishes IDs of different entities.
+/// Alternate flag `{:#}` displays hyphenated phrase.
+/// With `serde` feature it is serialized as a phrase.
+///
+/// ```
+/// use meme_id::{MemeId, SimplePhrase};
+///
+/// enum User {}
+///
+/// type UserId = MemeId<SimplePhrase, User>;
+///
+/// let id = UserId::new(42);
+/// let phrase = id.to_string();
+/// assert_eq!(phrase.parse::<UserId>(), Ok(id));
+/// assert_eq!(format!("{:#}", id), id.hyphenated().to_string());
+/// ```
+pub struct MemeId<S: Scheme, T = ()> {
+    id: S::Id,
+    tag: PhantomData<fn() -> T>,
+}
+
+impl<S, T> MemeId<S, T>
+where
+    S: Scheme,
+{
+    /// Wraps the ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ID has bits set above [`Scheme::BITS`].
+    /// See [`MemeId::try_new`].
+    #[inline]
+    pub fn new(id: S::Id) -> Self {
+        match Self::try_new(id) {
+            Some(id) => id,
+            None => panic!("ID doesn't fit into {} bits of the scheme", S::BITS),
+        }
+    }
+
+    /// Wraps the ID.
+    /// Returns `None` if the ID has bits set above [`Scheme::BITS`].
+    #[inline]
+    pub fn try_new(id: S::Id) -> Option<Self> {
+        if S::BITS < 128 && id.into() >> S::BITS != 0 {
+            return None;
+        }
+        Some(MemeId::wrap(id))
+    }
+
+    /// Wraps the ID decoded from a phrase, which always fits.
+    #[inline]
+    const fn wrap(id: S::Id) -> Self {
+        MemeId {
+            id,
+            tag: PhantomData,
+        }
+    }
+
+    /// Returns wrapped ID.
+    #[inline]
+    pub fn get(self) -> S::Id {
+        self.id
+    }
+
+    /// Returns phrase for the ID.
+    #[inline]
+    pub fn phrase(self) -> S {
+        S::encode(self.id)
+    }
+
+    /// Returns hyphenated phrase for the ID.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<S> {
+        Hyphenated(self.phrase())
+    }
+}
+
+impl<S, T> Clone for MemeId<S, T>
+where
+    S: Scheme,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, T> Copy for MemeId<S, T> where S: Scheme {}
+
+impl<S, T> PartialEq for MemeId<S, T>
+where
+    S: Scheme,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id.into() == other.id.into()
+    }
+}
+
+impl<S, T> Eq for MemeId<S, T> where S: Scheme {}
+
+impl<S, T> PartialOrd for MemeId<S, T>
+where
+    S: Scheme,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, T> Ord for MemeId<S, T>
+where
+    S: Scheme,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.id.into().cmp(&other.id.into())
+    }
+}
+
+impl<S, T> hash::Hash for MemeId<S, T>
+where
+    S: Scheme,
+{
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.id.into().hash(state)
+    }
+}
+
+impl<S, T> fmt::Debug for MemeId<S, T>
+where
+    S: Scheme,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MemeId")
+            .field(&self.id.into())
+            .field(&format_args!("{}", self.phrase()))
+            .finish()
+    }
+}
+
+impl<S, T> fmt::Display for MemeId<S, T>
+where
+    S: Scheme,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            self.hyphenated().fmt(f)
+        } else {
+            self.phrase().fmt(f)
+        }
+    }
+}
+
+impl<S, T> FromStr for MemeId<S, T>
+where
+    S: Scheme,
+{
+    type Err = ParseIdError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, ParseIdError> {
+        S::decode(s).map(MemeId::wrap).map_err(ParseIdError::from)
+    }
+}
+
+/// Error returned when [`MemeId`] fails to parse.
+///
+/// Same as [`Error`] without the word that failed to match,
+/// since it borrows the parsed string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseIdError {
+    NotEnoughWords { expected: usize, actual: usize },
+    TrailingWords,
+    Unrecognized { class: WordClass },
+    Ambiguous { class: WordClass },
+    ChecksumMismatch,
+    InvalidLength,
+    Overflow,
+    BufferTooSmall { required: usize },
+}
+
+impl From<Error<'_>> for ParseIdError {
+    fn from(err: Error<'_>) -> Self {
+        match err {
+            Error::NotEnoughWords { expected, actual } => {
+                ParseIdError::NotEnoughWords { expected, actual }
+            }
+            Error::TrailingWords => ParseIdError::TrailingWords,
+            Error::Unrecognized { class, .. } => ParseIdError::Unrecognized { class },
+            Error::Ambiguous { class, .. } => ParseIdError::Ambiguous { class },
+            Error::ChecksumMismatch => ParseIdError::ChecksumMismatch,
+            Error::InvalidLength => ParseIdError::InvalidLength,
+            Error::Overflow => ParseIdError::Overflow,
+            Error::BufferTooSmall { required } => ParseIdError::BufferTooSmall { required },
+        }
+    }
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseIdError::NotEnoughWords { expected, actual } => {
+                write!(
+                    f,
+                    "Not enough words. Expected {}, actual {}",
+                    expected, actual
+                )
+            }
+            ParseIdError::TrailingWords => {
+                write!(f, "Words left after parsing")
+            }
+            ParseIdError::Unrecognized { class } => {
+                write!(f, "Word unrecognized, expected {}", class)
+            }
+            ParseIdError::Ambiguous { class } => {
+                write!(f, "Word is ambiguous, expected {}", class)
+            }
+            ParseIdError::ChecksumMismatch => {
+                write!(f, "Checksum word doesn't match")
+            }
+            ParseIdError::InvalidLength => {
+                write!(f, "Words don't encode whole number of bytes")
+            }
+            ParseIdError::Overflow => {
+                write!(f, "Words encode number too large")
+            }
+            ParseIdError::BufferTooSmall { required } => {
+                write!(f, "Output buffer is too small. Required {} bytes", required)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S, T> serde::Serialize for MemeId<S, T>
+where
+    S: Scheme,
+{
+    fn serialize<R>(&self, serializer: R) -> Result<R::Ok, R::Error>
+    where
+        R: serde::Serializer,
+    {
+        serializer.collect_str(&self.phrase())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, S, T> serde::Deserialize<'de> for MemeId<S, T>
+where
+    S: Scheme,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::schemes::deserialize_phrase(deserializer, |s| S::decode(s).map(MemeId::wrap))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::{AdjectiveNounVerb, ComplexPhrase, CompoundPhrase, SimplePhrase};
+
+    #[test]
+    fn try_new() {
+        assert!(MemeId::<AdjectiveNounVerb>::try_new(0xff_ffff).is_some());
+        assert!(MemeId::<AdjectiveNounVerb>::try_new(0x100_0000).is_none());
+        assert!(MemeId::<CompoundPhrase>::try_new(u64::MAX).is_none());
+        assert!(MemeId::<SimplePhrase>::try_new(u32::MAX).is_some());
+        assert!(MemeId::<ComplexPhrase>::try_new(u128::MAX).is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_panics_on_wide_id() {
+        MemeId::<CompoundPhrase>::new(1 << 48);
+    }
+
+    #[test]
+    fn parses() {
+        let id = MemeId::<CompoundPhrase>::new((1 << 48) - 1);
+        assert_eq!(id.to_string().parse(), Ok(id));
+        assert_eq!(id.hyphenated().to_string().parse(), Ok(id));
+        assert_eq!(
+            "The".parse::<MemeId<CompoundPhrase>>(),
+            Err(ParseIdError::NotEnoughWords {
+                expected: 6,
+                actual: 0
+            })
+        );
+    }
+}
@@ -4,11 +4,172 @@
 
 #![no_std]
 
-#[cfg(feature = "serde")]
-extern crate alloc;
-
-mod dict;
-mod mapper;
+pub mod dict;
+mod id;
+pub mod mapper;
+pub mod matcher;
 mod schemes;
 
-pub use self::schemes::*;
+pub use self::{
+    id::{MemeId, ParseIdError},
+    mapper::WordMapper,
+    schemes::*,
+};
+
+/// Derives `Display`, `FromStr`, `Debug` and, with `#[meme_id(serde)]`, serde traits
+/// for a newtype ID using phrases of the scheme module.
+///
+/// ```
+/// use meme_id::MemeId;
+///
+/// #[derive(Clone, Copy, PartialEq, MemeId)]
+/// #[meme_id(scheme = "simple_phrase")]
+/// struct OrderId(u32);
+///
+/// let phrase = OrderId(42).to_string();
+/// assert_eq!(phrase, "The hearty koolie limbers learnedly");
+/// assert_eq!(phrase.parse(), Ok(OrderId(42)));
+/// assert_eq!(
+///     format!("{:?}", OrderId(42)),
+///     "OrderId(42, The hearty koolie limbers learnedly)"
+/// );
+///
+/// #[derive(Clone, Copy, PartialEq, MemeId)]
+/// #[meme_id(scheme = "simple_phrase", style = "hyphenated")]
+/// struct ShortId(u32);
+///
+/// assert_eq!(ShortId(42).to_string(), "hearty-koolie-limbers-learnedly");
+/// assert_eq!("hearty-koolie-limbers-learnedly".parse(), Ok(ShortId(42)));
+/// ```
+///
+/// Serialized phrases are written in the same style as `Display` writes them.
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// use meme_id::MemeId;
+///
+/// #[derive(Clone, Copy, PartialEq, MemeId)]
+/// #[meme_id(scheme = "simple_phrase", style = "hyphenated", serde)]
+/// struct ShortId(u32);
+///
+/// let json = serde_json::to_string(&ShortId(42)).unwrap();
+/// assert_eq!(json, r#""hearty-koolie-limbers-learnedly""#);
+/// assert_eq!(serde_json::from_str::<ShortId>(&json).unwrap(), ShortId(42));
+/// ```
+///
+/// Scheme must be specified and known.
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, meme_id::MemeId)]
+/// struct OrderId(u32);
+/// ```
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, meme_id::MemeId)]
+/// #[meme_id(scheme = "no_such_phrase")]
+/// struct OrderId(u32);
+/// ```
+///
+/// Only tuple structs with single field are supported.
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, meme_id::MemeId)]
+/// #[meme_id(scheme = "simple_phrase")]
+/// struct OrderId {
+///     id: u32,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, meme_id::MemeId)]
+/// #[meme_id(scheme = "simple_phrase")]
+/// struct OrderId(u16, u16);
+/// ```
+///
+/// Field type must fit into the scheme.
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, meme_id::MemeId)]
+/// #[meme_id(scheme = "simple_phrase")]
+/// struct OrderId(u64);
+/// ```
+#[cfg(feature = "derive")]
+pub use meme_id_derive::MemeId;
+
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    use crate::Scheme;
+
+    /// Phrase returned by `encode` function of a scheme module.
+    /// Schemes for IDs narrower than their integer type return `Option`.
+    pub trait Encoded {
+        type Phrase: Scheme;
+
+        fn phrase(self) -> Option<Self::Phrase>;
+    }
+
+    impl<S> Encoded for S
+    where
+        S: Scheme,
+    {
+        type Phrase = S;
+
+        #[inline]
+        fn phrase(self) -> Option<S> {
+            Some(self)
+        }
+    }
+
+    impl<S> Encoded for Option<S>
+    where
+        S: Scheme,
+    {
+        type Phrase = S;
+
+        #[inline]
+        fn phrase(self) -> Option<S> {
+            self
+        }
+    }
+
+    /// Returns width of the scheme of the module's `encode` function.
+    pub const fn bits<I, E: Encoded>(_encode: fn(I) -> E) -> u32 {
+        E::Phrase::BITS
+    }
+
+    /// Serializes phrase the same way as derived `Display` writes it.
+    #[cfg(feature = "serde")]
+    pub fn serialize_phrase<E, S>(
+        encoded: E,
+        hyphenated: bool,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        E: Encoded,
+        S: serde::Serializer,
+    {
+        match encoded.phrase() {
+            None => Err(serde::ser::Error::custom(format_args!(
+                "ID doesn't fit into {} bits",
+                E::Phrase::BITS
+            ))),
+            Some(phrase) if hyphenated => serializer.collect_str(&phrase.hyphenated()),
+            Some(phrase) => serializer.collect_str(&phrase),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn deserialize_phrase<'de, T, D>(
+        deserializer: D,
+        decode: impl FnOnce(&str) -> Result<T, crate::Error<'_>>,
+    ) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::schemes::deserialize_phrase(deserializer, decode)
+    }
+}
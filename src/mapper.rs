@@ -1,19 +1,33 @@
-use core::{
-    cmp::{Ord, Ordering},
-    ops::ControlFlow,
-};
+//!
+//! Mapping between bits and words.
+//!
 
+use core::{cmp::Ordering, fmt};
+
+/// Maps bits to words from an array and back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct WordMapper<'a> {
     array: &'a [&'a str],
 }
 
 impl<'a> WordMapper<'a> {
     /// Returns new world mapper instance from particularly ordered array of words.
-    pub(crate) const fn new(array: &'a [&'a str]) -> Self {
+    ///
+    /// Number of words must be a power of two.
+    /// Words must be sorted ignoring ASCII case and stored in Eytzinger order,
+    /// otherwise decoding will fail to find them.
+    /// Use [`eytzinger_layout`] to arrange words properly.
+    pub const fn new(array: &'a [&'a str]) -> Self {
         assert!(array.len().is_power_of_two());
         WordMapper { array }
     }
 
+    /// Returns words of this mapper in Eytzinger order.
+    #[inline]
+    pub const fn words(&self) -> &'a [&'a str] {
+        self.array
+    }
+
     /// Returns number of bits that can be encoded by word with this mapper.
     #[inline]
     pub const fn bits(&self) -> u32 {
@@ -26,48 +40,200 @@ impl<'a> WordMapper<'a> {
         self.array.len() - 1
     }
 
-    /// Returns bits for the specified word.
+    /// Returns index of the specified word.
+    /// Words are compared ignoring ASCII case.
     #[inline]
-    pub fn decode_word(&self, word: &str, bits: u128) -> Option<u128> {
-        let idx = eytzinger_search(self.array, word)?;
-        Some(bits << self.bits() | idx as u128)
+    pub fn find(&self, word: &str) -> Option<usize> {
+        eytzinger_search(self.array, word)
     }
 
-    /// Returns bits for the specified word.
+    /// Returns index of the word closest to the specified one by edit distance
+    /// and the distance.
+    /// Words are compared ignoring ASCII case.
+    ///
+    /// Returns `Err` with the distance if several words are equally close.
+    pub fn find_nearest(&self, word: &str) -> Result<(usize, usize), usize> {
+        let mut nearest = 0;
+        let mut distance = usize::MAX;
+        let mut ambiguous = false;
+
+        for (idx, candidate) in self.array.iter().enumerate() {
+            if candidate.len().abs_diff(word.len()) > distance {
+                continue;
+            }
+            let d = edit_distance(candidate, word);
+            if d < distance {
+                nearest = idx;
+                distance = d;
+                ambiguous = false;
+            } else if d == distance {
+                ambiguous = true;
+            }
+        }
+
+        if ambiguous {
+            Err(distance)
+        } else {
+            Ok((nearest, distance))
+        }
+    }
+
+    /// Returns index of the only word that starts with the specified prefix.
+    /// Word equal to the prefix is preferred over longer words.
+    /// Words are compared ignoring ASCII case.
+    ///
+    /// Returns `Err` with number of words starting with the prefix
+    /// if there's not exactly one.
+    pub fn find_by_prefix(&self, prefix: &str) -> Result<usize, usize> {
+        if let Some(idx) = self.find(prefix) {
+            return Ok(idx);
+        }
+
+        let mut found = 0;
+        let mut count = 0;
+        for (idx, word) in self.array.iter().enumerate() {
+            if common_prefix_len(word, prefix) == prefix.len() {
+                found = idx;
+                count += 1;
+            }
+        }
+
+        match count {
+            1 => Ok(found),
+            _ => Err(count),
+        }
+    }
+
+    /// Returns shortest prefix of the word that identifies it among words of this mapper.
+    /// See [`WordMapper::find_by_prefix`].
+    ///
+    /// Returns the word itself if it is a prefix of another word.
+    pub fn unique_prefix<'b>(&self, word: &'b str) -> &'b str {
+        let mut len = 1;
+        for other in self.array {
+            if !other.eq_ignore_ascii_case(word) {
+                len = len.max(common_prefix_len(word, other) + 1);
+            }
+        }
+        word.get(..len).unwrap_or(word)
+    }
+
+    /// Fills `out` with words closest to the specified one by edit distance,
+    /// closest first.
+    /// Words are compared ignoring ASCII case.
+    /// Words more than 64 edits away are considered equally far.
+    ///
+    /// Returns filled part of `out`.
+    pub fn find_nearest_n<'b>(&self, word: &str, out: &'b mut [&'a str]) -> &'b [&'a str] {
+        // Number of words in `out` with each distance.
+        // Words in `out` are ordered by distance, so this is enough to find insertion point.
+        let mut counts = [0; MAX_EDIT_LEN + 2];
+        let mut len = 0;
+        // Distance of the farthest word in `out` when it is full.
+        let mut worst = usize::MAX;
+
+        for candidate in self.array {
+            if len == out.len() && candidate.len().abs_diff(word.len()) >= worst {
+                continue;
+            }
+
+            let d = edit_distance(candidate, word).min(MAX_EDIT_LEN + 1);
+            let pos = counts[..=d].iter().sum::<usize>();
+
+            if pos == out.len() {
+                continue;
+            }
+
+            if len < out.len() {
+                len += 1;
+            } else {
+                counts[worst] -= 1;
+            }
+            out[pos..len].rotate_right(1);
+            out[pos] = candidate;
+            counts[d] += 1;
+
+            if len == out.len() {
+                worst = counts.iter().rposition(|&count| count > 0).unwrap_or(0);
+            }
+        }
+
+        &out[..len]
+    }
+
+    /// Returns bits for the word with specified index.
     #[inline]
-    pub fn decode_words<const N: usize>(
-        &self,
-        words: [&str; N],
-        mut bits: u128,
-    ) -> Result<u128, usize> {
+    pub fn decode_index(&self, idx: usize, bits: u128) -> u128 {
+        bits << self.bits() | (idx & self.bit_mask()) as u128
+    }
+
+    /// Returns bits for the words with specified indices.
+    #[inline]
+    pub fn decode_indices<const N: usize>(&self, indices: [usize; N], mut bits: u128) -> u128 {
         let mask = self.bit_mask();
         let shift = self.bits();
 
-        for (i, word) in words.iter().enumerate() {
-            let idx = eytzinger_search(self.array, word).ok_or(i)?;
+        for idx in indices.iter() {
             bits = (bits << shift) | (idx & mask) as u128;
         }
 
-        Ok(bits)
+        bits
     }
 
-    /// Returns bits for the specified word.
+    /// Returns bits for the words with specified indices.
     #[inline]
-    pub fn decode_words_norepeat<const N: usize>(
+    pub fn decode_indices_norepeat<const N: usize>(
         &self,
-        words: [&str; N],
+        indices: [usize; N],
         mut bits: u128,
-    ) -> Result<u128, usize> {
+    ) -> u128 {
         let less_bits_each = N.next_power_of_two().trailing_zeros();
         let mask = self.bit_mask() >> less_bits_each;
         let shift = self.bits() - less_bits_each;
 
-        for (i, word) in words.iter().enumerate() {
-            let idx = eytzinger_search(self.array, word).ok_or(i)?;
+        for idx in indices.iter() {
             bits = (bits << shift) | (idx & mask) as u128;
         }
 
-        Ok(bits)
+        bits
+    }
+
+    /// Returns bits for the specified word.
+    #[inline]
+    pub fn decode_word(&self, word: &str, bits: u128) -> Option<u128> {
+        let idx = self.find(word)?;
+        Some(self.decode_index(idx, bits))
+    }
+
+    /// Returns bits for the specified word.
+    #[inline]
+    pub fn decode_words<const N: usize>(
+        &self,
+        words: [&str; N],
+        bits: u128,
+    ) -> Result<u128, usize> {
+        let indices = self.find_all(words)?;
+        Ok(self.decode_indices(indices, bits))
+    }
+
+    /// Returns bits for the specified word.
+    #[inline]
+    pub fn decode_words_norepeat<const N: usize>(
+        &self,
+        words: [&str; N],
+        bits: u128,
+    ) -> Result<u128, usize> {
+        let indices = self.find_all(words)?;
+        Ok(self.decode_indices_norepeat(indices, bits))
+    }
+
+    #[inline]
+    fn find_all<const N: usize>(&self, words: [&str; N]) -> Result<[usize; N], usize> {
+        let mut indices = [0; N];
+        for (i, word) in words.iter().enumerate() {
+            indices[i] = self.find(word).ok_or(i)?;
+        }
+        Ok(indices)
     }
 
     /// Return word for the specified bits.
@@ -136,17 +302,485 @@ fn eytzinger_search(array: &[&str], s: &str) -> Option<usize> {
     }
 }
 
+/// Returns length of common prefix of words, ignoring ASCII case.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.bytes()
+        .zip(b.bytes())
+        .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+        .count()
+}
+
+/// Words longer than this are compared exactly by [`edit_distance`].
+const MAX_EDIT_LEN: usize = 64;
+
+/// Optimal string alignment distance between words, ignoring ASCII case.
+/// Counts insertions, deletions, substitutions and transpositions
+/// of adjacent characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+
+    if b.len() >= MAX_EDIT_LEN {
+        return if a.eq_ignore_ascii_case(b) {
+            0
+        } else {
+            usize::MAX
+        };
+    }
+
+    let eq = |x: u8, y: u8| x.eq_ignore_ascii_case(&y);
+
+    // Three last rows of the distance matrix.
+    let mut rows = [[0; MAX_EDIT_LEN]; 3];
+    for (j, d) in rows[0].iter_mut().enumerate().take(b.len() + 1) {
+        *d = j;
+    }
+
+    for i in 1..=a.len() {
+        let (cur, prev, prev2) = (i % 3, (i + 2) % 3, (i + 1) % 3);
+        rows[cur][0] = i;
+        for j in 1..=b.len() {
+            let cost = if eq(a[i - 1], b[j - 1]) { 0 } else { 1 };
+            let mut d = (rows[prev][j] + 1)
+                .min(rows[cur][j - 1] + 1)
+                .min(rows[prev][j - 1] + cost);
+            if i > 1 && j > 1 && eq(a[i - 1], b[j - 2]) && eq(a[i - 2], b[j - 1]) {
+                d = d.min(rows[prev2][j - 2] + 1);
+            }
+            rows[cur][j] = d;
+        }
+    }
+
+    rows[a.len() % 3][b.len()]
+}
+
 #[inline]
-fn cmp_ignore_case_ascii(a: &str, b: &str) -> Ordering {
-    let cf = a.bytes().zip(b.bytes()).try_for_each(|(a, b)| {
-        match Ord::cmp(&a.to_ascii_lowercase(), &b.to_ascii_lowercase()) {
-            Ordering::Equal => ControlFlow::Continue(()),
-            ord => ControlFlow::Break(ord),
+const fn cmp_ignore_case_ascii(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        let (a, b) = (a[i].to_ascii_lowercase(), b[i].to_ascii_lowercase());
+        if a != b {
+            return if a < b {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
         }
-    });
+        i += 1;
+    }
+
+    if a.len() < b.len() {
+        Ordering::Less
+    } else if a.len() > b.len() {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Error returned when words can't be laid out for [`WordMapper`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutError<'a> {
+    /// Number of words is not a power of two.
+    NotPowerOfTwo { len: usize },
+    /// Word is empty or contains characters other than ASCII letters.
+    NotAlphabetic { word: &'a str },
+    /// Word occurs more than once, ignoring ASCII case.
+    Duplicate { word: &'a str },
+}
+
+impl fmt::Display for LayoutError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::NotPowerOfTwo { len } => {
+                write!(f, "Number of words {} is not a power of two", len)
+            }
+            LayoutError::NotAlphabetic { word } => {
+                write!(f, "Word '{}' is not ASCII-alphabetic", word)
+            }
+            LayoutError::Duplicate { word } => {
+                write!(f, "Word '{}' is duplicated", word)
+            }
+        }
+    }
+}
+
+/// Arranges plain list of words into Eytzinger order expected by [`WordMapper`].
+///
+/// Panics if words can't be laid out. See [`try_eytzinger_layout`].
+///
+/// Intended to be used in constant context.
+///
+/// ```
+/// use meme_id::{mapper::eytzinger_layout, WordMapper};
+///
+/// static PETS: [&str; 4] = eytzinger_layout(["cat", "dog", "Ant", "fish"]);
+/// const PET_MAPPER: WordMapper<'static> = WordMapper::new(&PETS);
+///
+/// assert_eq!(PETS, ["dog", "cat", "fish", "Ant"]);
+/// ```
+pub const fn eytzinger_layout<const N: usize>(mut words: [&str; N]) -> [&str; N] {
+    let mut out = [""; N];
+    match try_eytzinger_layout(&mut words, &mut out) {
+        Ok(()) => out,
+        Err(LayoutError::NotPowerOfTwo { .. }) => panic!("Number of words is not a power of two"),
+        Err(LayoutError::NotAlphabetic { .. }) => panic!("Word is not ASCII-alphabetic"),
+        Err(LayoutError::Duplicate { .. }) => panic!("Word is duplicated"),
+    }
+}
+
+/// Arranges plain list of words into Eytzinger order expected by [`WordMapper`].
+///
+/// Number of words must be a power of two,
+/// each word must be non-empty and consist of ASCII letters only
+/// and words must be unique ignoring ASCII case.
+///
+/// Sorts `words` in place and writes them in Eytzinger order into `out`.
+///
+/// # Panics
+///
+/// Panics if `words` and `out` have different lengths.
+pub const fn try_eytzinger_layout<'a>(
+    words: &mut [&'a str],
+    out: &mut [&'a str],
+) -> Result<(), LayoutError<'a>> {
+    assert!(words.len() == out.len());
+
+    if !words.len().is_power_of_two() {
+        return Err(LayoutError::NotPowerOfTwo { len: words.len() });
+    }
+
+    let mut i = 0;
+    while i < words.len() {
+        let bytes = words[i].as_bytes();
+        if bytes.is_empty() {
+            return Err(LayoutError::NotAlphabetic { word: words[i] });
+        }
+        let mut j = 0;
+        while j < bytes.len() {
+            if !bytes[j].is_ascii_alphabetic() {
+                return Err(LayoutError::NotAlphabetic { word: words[i] });
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+
+    heap_sort(words);
+
+    let mut i = 1;
+    while i < words.len() {
+        if let Ordering::Equal = cmp_ignore_case_ascii(words[i - 1], words[i]) {
+            return Err(LayoutError::Duplicate { word: words[i] });
+        }
+        i += 1;
+    }
+
+    // In-order traversal of implicit tree with 1-based indices
+    // visits words in sorted order.
+    let n = words.len();
+    let mut k = 1;
+    while 2 * k <= n {
+        k *= 2;
+    }
+
+    let mut i = 0;
+    while i < n {
+        out[k - 1] = words[i];
+        i += 1;
+
+        if 2 * k < n {
+            k = 2 * k + 1;
+            while 2 * k <= n {
+                k *= 2;
+            }
+        } else {
+            while k & 1 == 1 {
+                k >>= 1;
+            }
+            k >>= 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sorts words ignoring ASCII case.
+/// Usable in constant context unlike `slice::sort`.
+const fn heap_sort(words: &mut [&str]) {
+    let n = words.len();
+
+    let mut i = n / 2;
+    while i > 0 {
+        i -= 1;
+        sift_down(words, i, n);
+    }
+
+    let mut end = n;
+    while end > 1 {
+        end -= 1;
+        swap(words, 0, end);
+        sift_down(words, 0, end);
+    }
+}
+
+/// Swaps two words. `slice::swap` is usable in constant context only since Rust 1.85.
+const fn swap(words: &mut [&str], a: usize, b: usize) {
+    let word = words[a];
+    words[a] = words[b];
+    words[b] = word;
+}
+
+const fn sift_down(words: &mut [&str], mut root: usize, end: usize) {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            break;
+        }
+        if child + 1 < end {
+            if let Ordering::Less = cmp_ignore_case_ascii(words[child], words[child + 1]) {
+                child += 1;
+            }
+        }
+        if let Ordering::Less = cmp_ignore_case_ascii(words[root], words[child]) {
+            swap(words, root, child);
+            root = child;
+        } else {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{string::String, vec::Vec};
+
+    use super::*;
+
+    fn words(n: usize) -> Vec<&'static str> {
+        include_str!("dict/preposition.txt")
+            .lines()
+            .take(n)
+            .collect()
+    }
+
+    fn layout<'a>(words: &[&'a str]) -> Result<Vec<&'a str>, LayoutError<'a>> {
+        let mut words = words.to_vec();
+        let mut out = std::vec![""; words.len()];
+        try_eytzinger_layout(&mut words, &mut out).map(|()| out)
+    }
+
+    #[test]
+    fn layout_empty() {
+        assert_eq!(layout(&[]), Err(LayoutError::NotPowerOfTwo { len: 0 }));
+    }
+
+    #[test]
+    fn layout_power_of_two() {
+        for k in 0..=6 {
+            let words = words(1 << k);
+            let array = layout(&words).unwrap();
+            let mapper = WordMapper::new(&array);
+
+            for word in &words {
+                let idx = mapper.find(word).unwrap();
+                assert_eq!(array[idx], *word);
+                assert_eq!(mapper.find(&word.to_ascii_uppercase()), Some(idx));
+            }
+            assert_eq!(mapper.find(""), None);
+            assert_eq!(mapper.find("a"), None);
+            assert_eq!(mapper.find("zzz"), None);
+        }
+    }
+
+    #[test]
+    fn layout_not_power_of_two() {
+        for k in 2..=6 {
+            let len = (1 << k) - 1;
+            assert_eq!(layout(&words(len)), Err(LayoutError::NotPowerOfTwo { len }));
+        }
+    }
+
+    #[test]
+    fn layout_duplicates() {
+        assert!(matches!(
+            layout(&["cat", "dog", "Cat", "fish"]),
+            Err(LayoutError::Duplicate { word }) if word.eq_ignore_ascii_case("cat")
+        ));
+
+        let mut words = words(64);
+        words[63] = "Out";
+        assert!(matches!(
+            layout(&words),
+            Err(LayoutError::Duplicate { word }) if word.eq_ignore_ascii_case("out")
+        ));
+    }
+
+    #[test]
+    fn layout_not_alphabetic() {
+        assert_eq!(
+            layout(&["cat", "d0g"]),
+            Err(LayoutError::NotAlphabetic { word: "d0g" })
+        );
+        assert_eq!(
+            layout(&["cat", ""]),
+            Err(LayoutError::NotAlphabetic { word: "" })
+        );
+    }
+
+    #[test]
+    fn edit_distance_osa() {
+        assert_eq!(edit_distance("horse", "horse"), 0);
+        assert_eq!(edit_distance("horse", "HoRsE"), 0);
+        assert_eq!(edit_distance("horse", "hrose"), 1);
+        assert_eq!(edit_distance("horse", "horsse"), 1);
+        assert_eq!(edit_distance("horse", "hose"), 1);
+        assert_eq!(edit_distance("horse", "house"), 1);
+        assert_eq!(edit_distance("horse", "ohrsee"), 2);
+        assert_eq!(edit_distance("", "horse"), 5);
+        assert_eq!(edit_distance("horse", ""), 5);
+        // Optimal string alignment doesn't edit transposed characters again.
+        assert_eq!(edit_distance("ca", "abc"), 3);
+    }
+
+    #[test]
+    fn edit_distance_long_words() {
+        let word = |len: usize, last: char| {
+            let mut word = "a".repeat(len - 1);
+            word.push(last);
+            word
+        };
+
+        let short: String = word(MAX_EDIT_LEN - 1, 'a');
+        assert_eq!(edit_distance(&short, &word(MAX_EDIT_LEN - 1, 'b')), 1);
+        assert_eq!(edit_distance(&short, &word(MAX_EDIT_LEN, 'a')), 1);
+
+        let long = word(MAX_EDIT_LEN, 'a');
+        assert_eq!(edit_distance(&long, &long.to_ascii_uppercase()), 0);
+        assert_eq!(edit_distance(&long, &word(MAX_EDIT_LEN, 'b')), usize::MAX);
+        assert_eq!(
+            edit_distance(&long, &word(MAX_EDIT_LEN + 1, 'a')),
+            usize::MAX
+        );
+    }
+
+    static PREFIXED: [&str; 8] = eytzinger_layout([
+        "cat", "catalog", "cut", "dog", "horse", "hose", "sheep", "shepherd",
+    ]);
+
+    #[test]
+    fn find_by_prefix() {
+        let mapper = WordMapper::new(&PREFIXED);
+        let found = |prefix| mapper.find_by_prefix(prefix).map(|idx| PREFIXED[idx]);
+
+        assert_eq!(found("d"), Ok("dog"));
+        assert_eq!(found("hor"), Ok("horse"));
+        assert_eq!(found("HoS"), Ok("hose"));
+        assert_eq!(found("cata"), Ok("catalog"));
+        assert_eq!(found("catalog"), Ok("catalog"));
+
+        assert_eq!(found("c"), Err(3));
+        assert_eq!(found("ho"), Err(2));
+        assert_eq!(found("she"), Err(2));
+        assert_eq!(found("cow"), Err(0));
+        assert_eq!(found("catalogs"), Err(0));
+    }
+
+    #[test]
+    fn find_by_prefix_prefers_whole_word() {
+        let mapper = WordMapper::new(&PREFIXED);
+        let found = |prefix| mapper.find_by_prefix(prefix).map(|idx| PREFIXED[idx]);
+
+        assert_eq!(found("cat"), Ok("cat"));
+        assert_eq!(found("CAT"), Ok("cat"));
+        assert_eq!(found("sheep"), Ok("sheep"));
+        assert_eq!(found("shep"), Ok("shepherd"));
+    }
+
+    #[test]
+    fn find_by_empty_prefix() {
+        let mapper = WordMapper::new(&PREFIXED);
+        assert_eq!(mapper.find_by_prefix(""), Err(PREFIXED.len()));
+
+        static SINGLE: [&str; 1] = ["cat"];
+        assert_eq!(WordMapper::new(&SINGLE).find_by_prefix(""), Ok(0));
+    }
+
+    #[test]
+    fn unique_prefix() {
+        let mapper = WordMapper::new(&PREFIXED);
+
+        assert_eq!(mapper.unique_prefix("dog"), "d");
+        assert_eq!(mapper.unique_prefix("horse"), "hor");
+        assert_eq!(mapper.unique_prefix("Hose"), "Hos");
+        assert_eq!(mapper.unique_prefix("catalog"), "cata");
+        assert_eq!(mapper.unique_prefix("shepherd"), "shep");
+        assert_eq!(mapper.unique_prefix("cat"), "cat");
+        assert_eq!(mapper.unique_prefix("sheep"), "shee");
+        assert_eq!(mapper.unique_prefix(""), "");
+
+        for word in &PREFIXED {
+            let prefix = mapper.unique_prefix(word);
+            assert_eq!(mapper.find_by_prefix(prefix), mapper.find(word).ok_or(0));
+        }
+    }
+
+    #[test]
+    fn find_nearest_n() {
+        let mapper = WordMapper::new(&PREFIXED);
+        let nearest = |word, n| {
+            let mut out = [""; 16];
+            mapper.find_nearest_n(word, &mut out[..n]).to_vec()
+        };
+
+        assert_eq!(nearest("hose", 1), ["hose"]);
+        assert_eq!(nearest("horsee", 2), ["horse", "hose"]);
+        assert_eq!(nearest("CAT", 2), ["cat", "cut"]);
+        assert_eq!(nearest("catalo", 2), ["catalog", "cat"]);
+        assert_eq!(nearest("shepard", 2), ["shepherd", "sheep"]);
+        assert_eq!(nearest("dog", 0), [""; 0]);
+    }
+
+    #[test]
+    fn find_nearest_n_is_ordered() {
+        let mapper = WordMapper::new(&PREFIXED);
+        for word in ["", "c", "cattle", "hoarse", "shhep", "zebra"] {
+            for n in 0..=PREFIXED.len() {
+                let mut out = [""; 8];
+                let found = mapper.find_nearest_n(word, &mut out[..n]);
+                assert_eq!(found.len(), n);
+
+                let distances: Vec<_> = found.iter().map(|w| edit_distance(w, word)).collect();
+                assert!(distances.windows(2).all(|d| d[0] <= d[1]), "{}", word);
+
+                // No word left out is closer than the farthest one found.
+                if let Some(&farthest) = distances.last() {
+                    for other in &PREFIXED {
+                        if !found.contains(other) {
+                            assert!(edit_distance(other, word) >= farthest, "{}", word);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn find_nearest_n_longer_than_dictionary() {
+        let mapper = WordMapper::new(&PREFIXED);
+        let mut out = [""; 12];
+        let found = mapper.find_nearest_n("cat", &mut out);
+        assert_eq!(found.len(), PREFIXED.len());
+        assert_eq!(found[..3], ["cat", "cut", "dog"]);
 
-    match cf {
-        ControlFlow::Break(ord) => ord,
-        _ => Ord::cmp(&a.len(), &b.len()),
+        let mut sorted = found.to_vec();
+        sorted.sort_unstable();
+        let mut words = PREFIXED.to_vec();
+        words.sort_unstable();
+        assert_eq!(sorted, words);
     }
 }
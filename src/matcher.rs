@@ -0,0 +1,172 @@
+//!
+//! Strategies to match words of a phrase with dictionary words.
+//!
+
+use crate::{dict::WordClass, mapper::WordMapper, Error};
+
+/// Reason why word was not matched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mismatch {
+    /// No dictionary word matches.
+    Unrecognized,
+    /// Several dictionary words match equally well.
+    Ambiguous,
+}
+
+impl Mismatch {
+    pub(crate) fn into_error(self, word: &str, class: WordClass) -> Error<'_> {
+        match self {
+            Mismatch::Unrecognized => Error::Unrecognized { word, class },
+            Mismatch::Ambiguous => Error::Ambiguous { word, class },
+        }
+    }
+}
+
+/// Strategy to match words of a phrase with dictionary words.
+pub trait Matcher {
+    /// Returns index of the dictionary word that matches the specified word.
+    fn find(&mut self, mapper: &WordMapper<'_>, word: &str) -> Result<usize, Mismatch>;
+}
+
+/// Matches words exactly, ignoring ASCII case.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Exact;
+
+impl Matcher for Exact {
+    #[inline]
+    fn find(&mut self, mapper: &WordMapper<'_>, word: &str) -> Result<usize, Mismatch> {
+        mapper.find(word).ok_or(Mismatch::Unrecognized)
+    }
+}
+
+/// Matches words by unique prefix, ignoring ASCII case.
+/// See [`WordMapper::find_by_prefix`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Prefix;
+
+impl Matcher for Prefix {
+    #[inline]
+    fn find(&mut self, mapper: &WordMapper<'_>, word: &str) -> Result<usize, Mismatch> {
+        match mapper.find_by_prefix(word) {
+            Ok(idx) => Ok(idx),
+            Err(0) => Err(Mismatch::Unrecognized),
+            Err(_) => Err(Mismatch::Ambiguous),
+        }
+    }
+}
+
+/// Matches words with the nearest dictionary word by edit distance.
+/// Keeps track of corrections it makes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fuzzy {
+    corrections: usize,
+    max_distance: usize,
+}
+
+impl Fuzzy {
+    /// Returns new fuzzy matcher that made no corrections yet.
+    #[inline]
+    pub const fn new() -> Self {
+        Fuzzy {
+            corrections: 0,
+            max_distance: 0,
+        }
+    }
+
+    /// Returns number of words corrected so far.
+    #[inline]
+    pub const fn corrections(&self) -> usize {
+        self.corrections
+    }
+
+    /// Returns confidence in the corrections made so far.
+    #[inline]
+    pub const fn confidence(&self) -> Confidence {
+        match self.max_distance {
+            0 => Confidence::Exact,
+            1 => Confidence::High,
+            _ => Confidence::Low,
+        }
+    }
+}
+
+impl Matcher for Fuzzy {
+    fn find(&mut self, mapper: &WordMapper<'_>, word: &str) -> Result<usize, Mismatch> {
+        if let Some(idx) = mapper.find(word) {
+            return Ok(idx);
+        }
+
+        let (idx, distance) = mapper.find_nearest(word).map_err(|_| Mismatch::Ambiguous)?;
+        self.corrections += 1;
+        self.max_distance = self.max_distance.max(distance);
+        Ok(idx)
+    }
+}
+
+/// Confidence in words corrected by [`Fuzzy`] matcher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Confidence {
+    /// All words matched exactly.
+    Exact,
+    /// Each corrected word is a single edit away from the dictionary word.
+    High,
+    /// Some corrected words are more than a single edit away from the dictionary word.
+    Low,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::eytzinger_layout;
+
+    static WORDS: [&str; 4] = eytzinger_layout(["cat", "cut", "horse", "sheep"]);
+    const MAPPER: WordMapper<'static> = WordMapper::new(&WORDS);
+
+    fn fuzzy(word: &str) -> (Result<&'static str, Mismatch>, Fuzzy) {
+        let mut fuzzy = Fuzzy::new();
+        let found = fuzzy.find(&MAPPER, word).map(|idx| WORDS[idx]);
+        (found, fuzzy)
+    }
+
+    #[test]
+    fn fuzzy_exact() {
+        let (found, fuzzy) = fuzzy("Horse");
+        assert_eq!(found, Ok("horse"));
+        assert_eq!(fuzzy.corrections(), 0);
+        assert_eq!(fuzzy.confidence(), Confidence::Exact);
+    }
+
+    #[test]
+    fn fuzzy_single_edit() {
+        for word in ["hrose", "horsse", "hose", "hoRSa"] {
+            let (found, fuzzy) = fuzzy(word);
+            assert_eq!(found, Ok("horse"), "{}", word);
+            assert_eq!(fuzzy.corrections(), 1);
+            assert_eq!(fuzzy.confidence(), Confidence::High);
+        }
+    }
+
+    #[test]
+    fn fuzzy_several_edits() {
+        let mut fuzzy = Fuzzy::new();
+        assert_eq!(
+            fuzzy.find(&MAPPER, "shep"),
+            Ok(MAPPER.find("sheep").unwrap())
+        );
+        assert_eq!(fuzzy.confidence(), Confidence::High);
+        assert_eq!(
+            fuzzy.find(&MAPPER, "ohrsee"),
+            Ok(MAPPER.find("horse").unwrap())
+        );
+        assert_eq!(fuzzy.corrections(), 2);
+        assert_eq!(fuzzy.confidence(), Confidence::Low);
+    }
+
+    #[test]
+    fn fuzzy_ambiguous() {
+        let (found, fuzzy) = fuzzy("cot");
+        assert_eq!(found, Err(Mismatch::Ambiguous));
+        assert_eq!(fuzzy.corrections(), 0);
+        assert_eq!(fuzzy.confidence(), Confidence::Exact);
+    }
+}
@@ -1,11 +1,12 @@
 use core::fmt;
 
 use crate::{
-    dict::{Adjective, Mapper, Noun},
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
     Hyphenated,
 };
 
-use super::{skip_one_of, string_to_words, Error};
+use super::{skip_one_of, string_to_words, Error, Scheme};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AdjectiveNoun {
@@ -33,17 +34,50 @@ impl fmt::Display for AdjectiveNoun {
     }
 }
 
-impl fmt::Display for Hyphenated<AdjectiveNoun> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}-{}", self.0.adjective, self.0.noun)
+impl Scheme for AdjectiveNoun {
+    type Id = u16;
+
+    const BITS: u32 = 16;
+    const WORDS: usize = 2;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: u16) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u16, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Adjective, self.adjective),
+            (WordClass::Noun, self.noun),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
     }
 }
 
 /// Encodes bits into `adjective-noun` scheme
 /// For 16-bit ids.
 pub fn encode(bits: u16) -> AdjectiveNoun {
-    let (adjective, bits) = Adjective::encode_word(bits.into());
-    let (noun, bits) = Noun::encode_word(bits);
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into `adjective-noun` scheme
+/// For 16-bit ids.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(bits: u16) -> AdjectiveNoun {
+    let (adjective, bits) = D::Adjective::encode_word(bits.into());
+    let (noun, bits) = D::Noun::encode_word(bits);
 
     debug_assert_eq!(bits, 0);
 
@@ -52,7 +86,24 @@ pub fn encode(bits: u16) -> AdjectiveNoun {
 
 /// Decodes `adjective-noun` scheme
 /// For 16-bit ids.
-pub fn decode(s: &str) -> Result<u16, Error> {
+pub fn decode(s: &str) -> Result<u16, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes `adjective-noun` scheme
+/// For 16-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u16, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes `adjective-noun` scheme
+/// For 16-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u16, Error<'a>> {
     let mut iter = string_to_words(s);
 
     skip_one_of(&mut iter, &["a", "the"]);
@@ -71,9 +122,8 @@ pub fn decode(s: &str) -> Result<u16, Error> {
     }
 
     let mut bits = 0;
-    bits = Noun::decode_word(noun, bits).ok_or(Error::Unrecognized { word: noun })?;
-    bits =
-        Adjective::decode_word(adjective, bits).ok_or(Error::Unrecognized { word: adjective })?;
+    bits = D::Noun::decode_word(noun, bits, matcher)?;
+    bits = D::Adjective::decode_word(adjective, bits, matcher)?;
     Ok(bits as u16)
 }
 
@@ -83,11 +133,8 @@ where
     T: Copy + Into<u16>,
     S: serde::ser::Serializer,
 {
-    use alloc::string::ToString;
-    use serde::Serialize;
-
     let an = encode((*value).into());
-    an.to_string().serialize(serializer)
+    serializer.collect_str(&an)
 }
 
 #[cfg(feature = "serde")]
@@ -96,11 +143,8 @@ where
     u16: Into<T>,
     D: serde::de::Deserializer<'de>,
 {
-    use alloc::borrow::Cow;
-
-    let s = <Cow<str> as serde::de::Deserialize>::deserialize(deserializer)?;
-    match decode(&*s) {
-        Err(err) => Err(serde::de::Error::custom(err)),
-        Ok(id) => Ok(id.into()),
-    }
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
 }
+
+#[cfg(feature = "serde")]
+super::with::modules!(AdjectiveNoun: u16, |id| Ok(super::encode(id)));
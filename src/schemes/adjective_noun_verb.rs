@@ -0,0 +1,216 @@
+use core::fmt;
+
+use crate::{
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
+    Hyphenated,
+};
+
+use super::{skip_one_of, string_to_words, Error, Scheme};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AdjectiveNounVerb {
+    pub adjective: &'static str,
+    pub noun: &'static str,
+    pub verb: &'static str,
+}
+
+impl AdjectiveNounVerb {
+    /// Encodes bits into `adjective noun verb` scheme
+    /// Returns `None` if bits above 24 are set.
+    #[inline]
+    pub fn try_encode(bits: u32) -> Option<Self> {
+        encode(bits)
+    }
+
+    /// Transform to hyphenated.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+}
+
+impl fmt::Display for AdjectiveNounVerb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The {} {} {}", self.adjective, self.noun, self.verb)
+    }
+}
+
+impl Scheme for AdjectiveNounVerb {
+    type Id = u32;
+
+    const BITS: u32 = 24;
+    const WORDS: usize = 3;
+
+    fn encode_with<D: Dictionary>(id: u32) -> Self {
+        let (adjective, bits) = D::Adjective::encode_word(id.into());
+        let (noun, bits) = D::Noun::encode_word(bits);
+        // Only 5 of 24 bits are left for the verb, higher bits are ignored.
+        let (verb, _) = D::VerbPlural::encode_word(bits & 0x1f);
+
+        AdjectiveNounVerb {
+            adjective,
+            noun,
+            verb,
+        }
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u32, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Adjective, self.adjective),
+            (WordClass::Noun, self.noun),
+            (WordClass::VerbPlural, self.verb),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
+    }
+}
+
+/// Encodes bits into `adjective-noun-verb` scheme
+/// For 24-bit ids.
+/// Returns `None` if bits above 24 are set.
+pub fn encode(bits: u32) -> Option<AdjectiveNounVerb> {
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into `adjective-noun-verb` scheme
+/// For 24-bit ids.
+/// Returns `None` if bits above 24 are set.
+/// Uses words from the specified dictionary.
+#[inline]
+pub fn encode_with<D: Dictionary>(bits: u32) -> Option<AdjectiveNounVerb> {
+    AdjectiveNounVerb::try_encode_with::<D>(bits)
+}
+
+/// Decodes `adjective-noun-verb` scheme
+/// For 24-bit ids.
+pub fn decode(s: &str) -> Result<u32, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes `adjective-noun-verb` scheme
+/// For 24-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u32, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes `adjective-noun-verb` scheme
+/// For 24-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u32, Error<'a>> {
+    let mut iter = string_to_words(s);
+
+    skip_one_of(&mut iter, &["a", "the"]);
+
+    let adjective = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 3,
+        actual: 0,
+    })?;
+    let noun = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 3,
+        actual: 1,
+    })?;
+    let verb = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 3,
+        actual: 2,
+    })?;
+
+    if iter.next().is_some() {
+        return Err(Error::TrailingWords);
+    }
+
+    let mut bits = 0;
+    bits = D::VerbPlural::decode_word(verb, bits, matcher)?;
+    bits = D::Noun::decode_word(noun, bits, matcher)?;
+    bits = D::Adjective::decode_word(adjective, bits, matcher)?;
+
+    // Verbs encode 9 bits but only 5 of them are used.
+    if bits >> 24 != 0 {
+        return Err(Error::Overflow);
+    }
+
+    Ok(bits as u32)
+}
+
+/// Serialization error for IDs that don't fit into the scheme.
+#[cfg(feature = "serde")]
+const OVERFLOW: &str = "ID doesn't fit into 24 bits";
+
+#[cfg(feature = "serde")]
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<u32>,
+    S: serde::ser::Serializer,
+{
+    match encode((*value).into()) {
+        None => Err(serde::ser::Error::custom(OVERFLOW)),
+        Some(an) => serializer.collect_str(&an),
+    }
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    u32: Into<T>,
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(AdjectiveNounVerb: u32, |id| super::encode(id).ok_or(super::OVERFLOW));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{format, string::ToString};
+
+    use super::*;
+    use crate::{
+        dict::{Plural, Verb},
+        schemes::sample_ids,
+    };
+
+    #[test]
+    fn round_trips() {
+        for id in sample_ids::<AdjectiveNounVerb>(1000) {
+            let phrase = encode(id).unwrap().to_string();
+            assert_eq!(decode(&phrase), Ok(id), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn rejects_wide_ids() {
+        assert!(encode(0xff_ffff).is_some());
+        assert_eq!(encode(0x100_0000), None);
+        assert_eq!(encode(u32::MAX), None);
+        assert_eq!(
+            AdjectiveNounVerb::encode(u32::MAX),
+            encode(0xff_ffff).unwrap()
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let phrase = encode(0xff_ffff).unwrap();
+        let (verb, _) = Verb::<Plural>::encode_word(0x20);
+        let phrase = format!("The {} {} {}", phrase.adjective, phrase.noun, verb);
+        assert_eq!(decode(&phrase), Err(Error::Overflow));
+    }
+}
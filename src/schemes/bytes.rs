@@ -0,0 +1,355 @@
+//!
+//! Encoding of byte slices of any length, such as hashes and public keys.
+//!
+//! Bytes are encoded into a sequence of sentences
+//! `The {adjective} {noun} {verb} {adverb} {preposition} the {adjective} {noun}.`
+//! Last sentence is cut short after the last word needed.
+//!
+//! Bits of the bytes are followed by a single set bit and zeros up to the end of the last word.
+//! Decoding finds that bit to recover the length.
+//!
+//! ```
+//! let hash = [0xde, 0xad, 0xbe, 0xef, 0x42];
+//! let phrase = meme_id::bytes::encode(&hash).to_string();
+//!
+//! let mut out = [0; 32];
+//! let len = meme_id::bytes::decode(&phrase, &mut out).unwrap();
+//! assert_eq!(&out[..len], &hash);
+//! ```
+//!
+
+use core::{fmt, marker::PhantomData, slice};
+
+use crate::{
+    dict::{Dictionary, Standard, WordClass},
+    matcher::{Exact, Matcher},
+};
+
+use super::{skip_one_of, string_to_words, Error, ARTICLES, SENTENCE};
+
+/// Phrase that encodes slice of bytes.
+pub struct Bytes<'b, D = Standard> {
+    bytes: &'b [u8],
+    dict: PhantomData<fn() -> D>,
+}
+
+impl<'b> Bytes<'b> {
+    /// Encodes bytes into a phrase.
+    #[inline]
+    pub fn encode(bytes: &'b [u8]) -> Self {
+        encode(bytes)
+    }
+}
+
+impl<'b, D> Bytes<'b, D> {
+    /// Returns bytes encoded by the phrase.
+    #[inline]
+    pub fn bytes(&self) -> &'b [u8] {
+        self.bytes
+    }
+}
+
+impl<'b, D> Bytes<'b, D>
+where
+    D: Dictionary,
+{
+    /// Calls closure for each word of the phrase with its class.
+    pub fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.words().try_for_each(|(class, word)| f(class, word))
+    }
+
+    fn words(&self) -> Words<'b, D> {
+        Words {
+            bytes: self.bytes.iter(),
+            acc: 0,
+            len: 0,
+            marked: false,
+            pos: 0,
+            dict: PhantomData,
+        }
+    }
+}
+
+impl<D> Clone for Bytes<'_, D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for Bytes<'_, D> {}
+
+impl<D> fmt::Debug for Bytes<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Bytes").field(&self.bytes).finish()
+    }
+}
+
+impl<D> fmt::Display for Bytes<'_, D>
+where
+    D: Dictionary,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (pos, (_, word)) in self.words().enumerate() {
+            match pos % SENTENCE.len() {
+                0 if pos == 0 => f.write_str("The ")?,
+                0 => f.write_str(". The ")?,
+                5 => f.write_str(" the ")?,
+                _ => f.write_str(" ")?,
+            }
+            f.write_str(word)?;
+        }
+        f.write_str(".")
+    }
+}
+
+/// Iterator over words that encode bytes.
+struct Words<'b, D> {
+    bytes: slice::Iter<'b, u8>,
+    /// Bits not yet encoded.
+    acc: u64,
+    /// Number of bits in `acc`.
+    len: u32,
+    /// Whether bit marking the end of bytes was added.
+    marked: bool,
+    pos: usize,
+    dict: PhantomData<fn() -> D>,
+}
+
+impl<D> Iterator for Words<'_, D>
+where
+    D: Dictionary,
+{
+    type Item = (WordClass, &'static str);
+
+    fn next(&mut self) -> Option<(WordClass, &'static str)> {
+        let class = SENTENCE[self.pos % SENTENCE.len()];
+        let mapper = D::word_mapper(class);
+
+        while self.len < mapper.bits() {
+            match self.bytes.next() {
+                Some(&byte) => {
+                    self.acc |= u64::from(byte) << self.len;
+                    self.len += 8;
+                }
+                None if !self.marked => {
+                    self.acc |= 1 << self.len;
+                    self.len += 1;
+                    self.marked = true;
+                }
+                None if self.len == 0 => return None,
+                None => break,
+            }
+        }
+
+        let (word, _) = mapper.encode_word(self.acc.into());
+        self.acc >>= mapper.bits();
+        self.len = self.len.saturating_sub(mapper.bits());
+        self.pos += 1;
+        Some((class, word))
+    }
+}
+
+/// Encodes bytes into a phrase.
+#[inline]
+pub fn encode(bytes: &[u8]) -> Bytes<'_> {
+    encode_with::<Standard>(bytes)
+}
+
+/// Encodes bytes into a phrase.
+/// Uses words from the specified dictionary.
+#[inline]
+pub fn encode_with<D: Dictionary>(bytes: &[u8]) -> Bytes<'_, D> {
+    Bytes {
+        bytes,
+        dict: PhantomData,
+    }
+}
+
+/// Decodes a phrase into `out`.
+/// Returns number of bytes decoded.
+///
+/// Fails with [`Error::BufferTooSmall`] with required length if `out` is too small.
+pub fn decode<'a>(s: &'a str, out: &mut [u8]) -> Result<usize, Error<'a>> {
+    decode_with::<Standard>(s, out)
+}
+
+/// Decodes a phrase into `out`.
+/// Returns number of bytes decoded.
+/// Uses words from the specified dictionary.
+///
+/// Fails with [`Error::BufferTooSmall`] with required length if `out` is too small.
+pub fn decode_with<'a, D: Dictionary>(s: &'a str, out: &mut [u8]) -> Result<usize, Error<'a>> {
+    decode_by::<D>(s, out, &mut Exact)
+}
+
+/// Decodes a phrase into `out`.
+/// Returns number of bytes decoded.
+/// Uses words from the specified dictionary matched by the matcher.
+///
+/// Fails with [`Error::BufferTooSmall`] with required length if `out` is too small.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    out: &mut [u8],
+    matcher: &mut impl Matcher,
+) -> Result<usize, Error<'a>> {
+    let mut iter = string_to_words(s);
+
+    let mut acc = 0u64;
+    let mut len = 0;
+    let mut last_bits = 0;
+    let mut written = 0;
+
+    // Last complete byte may be the one marking the end.
+    // It is held back until more bits follow.
+    let mut pending = None;
+
+    let mut pos = 0;
+    loop {
+        if ARTICLES.contains(&(pos % SENTENCE.len())) {
+            skip_one_of(&mut iter, &["a", "the"]);
+        }
+
+        let word = match iter.next() {
+            None => break,
+            Some(word) => word,
+        };
+
+        let class = SENTENCE[pos % SENTENCE.len()];
+        let mapper = D::word_mapper(class);
+        let idx = matcher
+            .find(&mapper, word)
+            .map_err(|mismatch| mismatch.into_error(word, class))?;
+
+        acc |= ((idx & mapper.bit_mask()) as u64) << len;
+        len += mapper.bits();
+        last_bits = mapper.bits();
+        pos += 1;
+
+        while len >= 8 {
+            if let Some(byte) = pending.replace(acc as u8) {
+                // Keep counting bytes past the end of `out` to report required length.
+                if let Some(slot) = out.get_mut(written) {
+                    *slot = byte;
+                }
+                written += 1;
+            }
+            acc >>= 8;
+            len -= 8;
+        }
+    }
+
+    if pos == 0 {
+        return Err(Error::NotEnoughWords {
+            expected: 1,
+            actual: 0,
+        });
+    }
+
+    // Marking bit must be within the last word.
+    let len = match pending {
+        Some(byte) if acc == 1 && len <= last_bits => {
+            if let Some(slot) = out.get_mut(written) {
+                *slot = byte;
+            }
+            written + 1
+        }
+        None if acc == 1 && len <= last_bits => written,
+        Some(1) if acc == 0 && len + 8 <= last_bits => written,
+        _ => return Err(Error::InvalidLength),
+    };
+
+    if len > out.len() {
+        return Err(Error::BufferTooSmall { required: len });
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{format, string::ToString};
+
+    use super::*;
+    use crate::dict::{Adjective, Mapper, Noun};
+
+    #[test]
+    fn round_trips() {
+        let mut bytes = [0; 64];
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        for byte in &mut bytes {
+            state = state.rotate_left(23).wrapping_mul(0x2545_f491_4f6c_dd1d);
+            *byte = (state >> 56) as u8;
+        }
+
+        for len in 0..=bytes.len() {
+            for bytes in [&bytes[..len], &[0; 64][..len], &[0xff; 64][..len]] {
+                let phrase = encode(bytes).to_string();
+                let mut out = [0; 64];
+                assert_eq!(decode(&phrase, &mut out), Ok(len), "{}", phrase);
+                assert_eq!(&out[..len], bytes, "{}", phrase);
+            }
+        }
+    }
+
+    #[test]
+    fn out_too_small() {
+        let phrase = encode(&[1, 2, 3]).to_string();
+        assert_eq!(
+            decode(&phrase, &mut [0; 2]),
+            Err(Error::BufferTooSmall { required: 3 })
+        );
+        assert_eq!(decode(&phrase, &mut [0; 3]), Ok(3));
+
+        for len in 1..=32 {
+            let bytes = [0xa5; 32];
+            let phrase = encode(&bytes[..len]).to_string();
+            for out_len in 0..len {
+                let mut out = [0; 32];
+                assert_eq!(
+                    decode(&phrase, &mut out[..out_len]),
+                    Err(Error::BufferTooSmall { required: len }),
+                    "{}",
+                    phrase
+                );
+            }
+        }
+
+        assert_eq!(decode(&encode(&[]).to_string(), &mut []), Ok(0));
+    }
+
+    #[test]
+    fn invalid_length() {
+        let (zero_adjective, _) = Adjective::encode_word(0);
+        let (zero_noun, _) = Noun::encode_word(0);
+
+        // No bit marks the end.
+        let phrase = format!("The {}.", zero_adjective);
+        assert_eq!(decode(&phrase, &mut [0; 8]), Err(Error::InvalidLength));
+
+        // Bit marking the end is followed by a word.
+        let phrase = encode(&[]).to_string();
+        let phrase = format!("{} {}.", phrase.trim_end_matches('.'), zero_noun);
+        assert_eq!(decode(&phrase, &mut [0; 8]), Err(Error::InvalidLength));
+
+        // Bits after the marking bit are set.
+        let (word, _) = Adjective::encode_word(0b11);
+        let phrase = format!("The {}.", word);
+        assert_eq!(decode(&phrase, &mut [0; 8]), Err(Error::InvalidLength));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            decode("", &mut [0; 8]),
+            Err(Error::NotEnoughWords {
+                expected: 1,
+                actual: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use core::fmt;
+
+use crate::{
+    dict::{Dictionary, Mapper, WordClass},
+    matcher::Matcher,
+};
+
+use super::{split_last_word, strip_last_one_of, Error, Scheme};
+
+/// Phrase of the scheme followed by a checksum word.
+///
+/// Checksum noun is derived from all bits of the ID,
+/// so substituted or swapped words are detected
+/// with probability of 511 in 512.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<S> {
+    pub phrase: S,
+    pub checksum: &'static str,
+}
+
+impl<S> fmt::Display for Checked<S>
+where
+    S: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, says the {}", self.phrase, self.checksum)
+    }
+}
+
+impl<S> Scheme for Checked<S>
+where
+    S: Scheme,
+{
+    type Id = S::Id;
+
+    const BITS: u32 = S::BITS;
+    const WORDS: usize = S::WORDS + 1;
+
+    fn encode_with<D: Dictionary>(id: S::Id) -> Self {
+        let (checksum, _) = D::Noun::encode_word(checksum(id.into()));
+
+        Checked {
+            phrase: S::encode_with::<D>(id),
+            checksum,
+        }
+    }
+
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<S::Id, Error<'a>> {
+        let (rest, checksum_word) = split_last_word(s).ok_or(Error::NotEnoughWords {
+            expected: Self::WORDS,
+            actual: 0,
+        })?;
+
+        let rest = strip_last_one_of(rest, &["a", "the"]);
+        let rest = strip_last_one_of(rest, &["says"]);
+
+        let id = match S::decode_by::<D>(rest, matcher) {
+            Err(Error::NotEnoughWords { expected, actual }) => {
+                return Err(Error::NotEnoughWords {
+                    expected: expected + 1,
+                    actual: actual + 1,
+                })
+            }
+            result => result?,
+        };
+
+        let bits = D::Noun::decode_word(checksum_word, 0, matcher)?;
+        if bits != checksum(id.into()) & D::Noun::WORD_MAPPER.bit_mask() as u128 {
+            return Err(Error::ChecksumMismatch);
+        }
+
+        Ok(id)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.phrase.for_each_word(&mut f)?;
+        f(WordClass::Noun, self.checksum)
+    }
+}
+
+/// Mixes all bits of the ID into low bits.
+fn checksum(bits: u128) -> u128 {
+    fn mix(mut x: u64) -> u64 {
+        x ^= x >> 30;
+        x = x.wrapping_mul(0xbf58476d1ce4e5b9);
+        x ^= x >> 27;
+        x = x.wrapping_mul(0x94d049bb133111eb);
+        x ^ (x >> 31)
+    }
+
+    mix(mix(bits as u64) ^ (bits >> 64) as u64) as u128
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::{
+        dict::Noun,
+        schemes::{
+            sample_ids, AdjectiveNoun, AdjectiveNounVerb, ComplexPhrase, CompoundPhrase,
+            DoublePhrase, Phrase, PrepositionPronoun, Punk, SimplePhrase, VarPhrase,
+        },
+    };
+
+    fn round_trip<S: Scheme>()
+    where
+        S::Id: fmt::Debug + PartialEq,
+    {
+        for id in sample_ids::<S>(200) {
+            let phrase = Checked::<S>::encode(id).to_string();
+            assert_eq!(Checked::<S>::decode(&phrase), Ok(id), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip::<AdjectiveNoun>();
+        round_trip::<PrepositionPronoun>();
+        round_trip::<AdjectiveNounVerb>();
+        round_trip::<SimplePhrase>();
+        round_trip::<CompoundPhrase>();
+        round_trip::<Phrase>();
+        round_trip::<Punk>();
+        round_trip::<DoublePhrase>();
+        round_trip::<ComplexPhrase>();
+        round_trip::<VarPhrase>();
+        #[cfg(feature = "snowflake")]
+        round_trip::<crate::schemes::SnowflakePhrase>();
+        #[cfg(feature = "ulid")]
+        round_trip::<crate::schemes::UlidPhrase>();
+        #[cfg(feature = "uuid")]
+        round_trip::<crate::schemes::UuidV4Phrase>();
+    }
+
+    #[test]
+    fn wrong_checksum() {
+        for id in sample_ids::<Phrase>(200) {
+            let mut phrase = Checked::<Phrase>::encode(id);
+            let bits = checksum(id.into()) + 1;
+            phrase.checksum = Noun::encode_word(bits).0;
+
+            let phrase = phrase.to_string();
+            assert_eq!(
+                Checked::<Phrase>::decode(&phrase),
+                Err(Error::ChecksumMismatch),
+                "{}",
+                phrase
+            );
+        }
+    }
+
+    #[test]
+    fn wrong_phrase() {
+        let mask = Noun::WORD_MAPPER.bit_mask() as u128;
+        for id in sample_ids::<Phrase>(200) {
+            // Checksum word detects substitution with probability of 511 in 512.
+            if checksum(id.into()) & mask == checksum((id ^ 1).into()) & mask {
+                continue;
+            }
+
+            let phrase = Checked {
+                phrase: Phrase::encode(id ^ 1),
+                checksum: Checked::<Phrase>::encode(id).checksum,
+            };
+
+            let phrase = phrase.to_string();
+            assert_eq!(
+                Checked::<Phrase>::decode(&phrase),
+                Err(Error::ChecksumMismatch),
+                "{}",
+                phrase
+            );
+        }
+    }
+}
@@ -1,29 +1,31 @@
 use core::fmt;
 
 use crate::{
-    dict::{Adjective, Adverb, Mapper, Noun, Plural, Preposition, Verb},
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
     Hyphenated,
 };
 
-use super::{skip_one_of, string_to_words, Error};
+use super::{skip_one_of, string_to_words, Error, Scheme};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ComplexPhrase {
-    adjective1: &'static str,
-    noun1: &'static str,
-    verb1: &'static str,
-    adverb1: &'static str,
-    preposition1: &'static str,
-    adjective2: &'static str,
-    noun2: &'static str,
-    preposition2: &'static str,
-    noun3: &'static str,
-    verb2: &'static str,
-    adverb2: &'static str,
-    adjective3: &'static str,
-    adjective4: &'static str,
-    noun4: &'static str,
-    verb3: &'static str,
-    verb4: &'static str,
+    pub adjective1: &'static str,
+    pub noun1: &'static str,
+    pub verb1: &'static str,
+    pub adverb1: &'static str,
+    pub preposition1: &'static str,
+    pub adjective2: &'static str,
+    pub noun2: &'static str,
+    pub preposition2: &'static str,
+    pub noun3: &'static str,
+    pub verb2: &'static str,
+    pub adverb2: &'static str,
+    pub adjective3: &'static str,
+    pub adjective4: &'static str,
+    pub noun4: &'static str,
+    pub verb3: &'static str,
+    pub verb4: &'static str,
 }
 
 impl ComplexPhrase {
@@ -65,39 +67,95 @@ impl fmt::Display for ComplexPhrase {
     }
 }
 
-impl fmt::Display for Hyphenated<ComplexPhrase> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}-{}-{}-{}-{}-{}-{}-{}-{}-{}-{}-{}-{}-{}-{}-{}",
-            self.0.adjective1,
-            self.0.noun1,
-            self.0.verb1,
-            self.0.adverb1,
-            self.0.preposition1,
-            self.0.adjective2,
-            self.0.noun2,
-            self.0.preposition2,
-            self.0.noun3,
-            self.0.verb2,
-            self.0.adverb2,
-            self.0.adjective3,
-            self.0.adjective4,
-            self.0.noun4,
-            self.0.verb3,
-            self.0.verb4,
-        )
+impl Scheme for ComplexPhrase {
+    type Id = u128;
+
+    const BITS: u32 = 128;
+    const WORDS: usize = 16;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: u128) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u128, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Adjective, self.adjective1),
+            (WordClass::Noun, self.noun1),
+            (WordClass::VerbPlural, self.verb1),
+            (WordClass::Adverb, self.adverb1),
+            (WordClass::Preposition, self.preposition1),
+            (WordClass::Adjective, self.adjective2),
+            (WordClass::Noun, self.noun2),
+            (WordClass::Preposition, self.preposition2),
+            (WordClass::Noun, self.noun3),
+            (WordClass::VerbPlural, self.verb2),
+            (WordClass::Adverb, self.adverb2),
+            (WordClass::Adjective, self.adjective3),
+            (WordClass::Adjective, self.adjective4),
+            (WordClass::Noun, self.noun4),
+            (WordClass::VerbPlural, self.verb3),
+            (WordClass::VerbPlural, self.verb4),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
     }
 }
 
+/// Offset and number of bits encoded by each word,
+/// in order of words in the phrase.
+#[cfg(feature = "ulid")]
+pub(super) const WORD_BITS: [(u32, u32); 16] = [
+    (0, 9),
+    (36, 8),
+    (68, 8),
+    (100, 8),
+    (116, 6),
+    (9, 9),
+    (44, 8),
+    (122, 6),
+    (52, 8),
+    (76, 8),
+    (108, 8),
+    (18, 9),
+    (27, 9),
+    (60, 8),
+    (84, 8),
+    (92, 8),
+];
+
 /// Encodes bits into a complex phrase.
 /// For 128-bit ids.
 pub fn encode(bits: u128) -> ComplexPhrase {
-    let ([adjective1, adjective2, adjective3, adjective4], bits) = Adjective::encode_words(bits);
-    let ([noun1, noun2, noun3, noun4], bits) = Noun::encode_words(bits);
-    let ([verb1, verb2, verb3, verb4], bits) = Verb::<Plural>::encode_words(bits);
-    let ([adverb1, adverb2], bits) = Adverb::encode_words(bits);
-    let ([preposition1, preposition2], bits) = Preposition::encode_words(bits);
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into a complex phrase.
+/// For 128-bit ids.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(bits: u128) -> ComplexPhrase {
+    // Words of the same class are encoded in pairs and prepositions may repeat.
+    // Excluding repeats within larger groups would not leave room for 128 bits.
+    let ([adjective1, adjective2], bits) = D::Adjective::encode_words(bits);
+    let ([adjective3, adjective4], bits) = D::Adjective::encode_words(bits);
+    let ([noun1, noun2], bits) = D::Noun::encode_words(bits);
+    let ([noun3, noun4], bits) = D::Noun::encode_words(bits);
+    let ([verb1, verb2], bits) = D::VerbPlural::encode_words(bits);
+    let ([verb3, verb4], bits) = D::VerbPlural::encode_words(bits);
+    let ([adverb1, adverb2], bits) = D::Adverb::encode_words(bits);
+    let (preposition1, bits) = D::Preposition::encode_word(bits);
+    let (preposition2, bits) = D::Preposition::encode_word(bits);
     debug_assert_eq!(bits, 0);
 
     ComplexPhrase {
@@ -122,7 +180,99 @@ pub fn encode(bits: u128) -> ComplexPhrase {
 
 /// Decodes a complex phrase.
 /// For 128-bit ids.
-pub fn decode(s: &str) -> Result<u128, Error> {
+pub fn decode(s: &str) -> Result<u128, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes a complex phrase.
+/// For 128-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u128, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes a complex phrase.
+/// For 128-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u128, Error<'a>> {
+    let Words {
+        adjectives: [adjective1, adjective2, adjective3, adjective4],
+        nouns: [noun1, noun2, noun3, noun4],
+        verbs: [verb1, verb2, verb3, verb4],
+        adverbs: [adverb1, adverb2],
+        prepositions: [preposition1, preposition2],
+    } = split_words(s)?;
+
+    let mut bits = 0;
+
+    bits = D::Preposition::decode_word(preposition2, bits, matcher)?;
+    bits = D::Preposition::decode_word(preposition1, bits, matcher)?;
+    bits = D::Adverb::decode_words([adverb2, adverb1], bits, matcher)?;
+    bits = D::VerbPlural::decode_words([verb4, verb3], bits, matcher)?;
+    bits = D::VerbPlural::decode_words([verb2, verb1], bits, matcher)?;
+    bits = D::Noun::decode_words([noun4, noun3], bits, matcher)?;
+    bits = D::Noun::decode_words([noun2, noun1], bits, matcher)?;
+    bits = D::Adjective::decode_words([adjective4, adjective3], bits, matcher)?;
+    bits = D::Adjective::decode_words([adjective2, adjective1], bits, matcher)?;
+
+    Ok(bits)
+}
+
+/// Decodes a complex phrase in the layout used before all 128 bits were encoded.
+/// Legacy layout kept only lower 114 bits of ids.
+///
+/// Legacy phrases decode to unrelated ids with [`decode`].
+/// Decode them with this function and encode again to migrate.
+pub fn decode_legacy(s: &str) -> Result<u128, Error<'_>> {
+    decode_legacy_by::<Standard>(s, &mut Exact)
+}
+
+/// Decodes a complex phrase in the legacy layout.
+/// See [`decode_legacy`].
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_legacy_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u128, Error<'a>> {
+    let Words {
+        adjectives: [adjective1, adjective2, adjective3, adjective4],
+        nouns: [noun1, noun2, noun3, noun4],
+        verbs: [verb1, verb2, verb3, verb4],
+        adverbs: [adverb1, adverb2],
+        prepositions: [preposition1, preposition2],
+    } = split_words(s)?;
+
+    let mut bits = 0;
+
+    let prepositions = [preposition2, preposition1];
+    let adverbs = [adverb2, adverb1];
+    let verbs = [verb4, verb3, verb2, verb1];
+    let nouns = [noun4, noun3, noun2, noun1];
+    let adjectives = [adjective4, adjective3, adjective2, adjective1];
+
+    bits = D::Preposition::decode_words(prepositions, bits, matcher)?;
+    bits = D::Adverb::decode_words(adverbs, bits, matcher)?;
+    bits = D::VerbPlural::decode_words(verbs, bits, matcher)?;
+    bits = D::Noun::decode_words(nouns, bits, matcher)?;
+    bits = D::Adjective::decode_words(adjectives, bits, matcher)?;
+
+    Ok(bits)
+}
+
+/// Words of a complex phrase grouped by class.
+struct Words<'a> {
+    adjectives: [&'a str; 4],
+    nouns: [&'a str; 4],
+    verbs: [&'a str; 4],
+    adverbs: [&'a str; 2],
+    prepositions: [&'a str; 2],
+}
+
+/// Splits a complex phrase into words, skipping auxiliary words.
+fn split_words(s: &str) -> Result<Words<'_>, Error<'_>> {
     let mut iter = string_to_words(s);
 
     skip_one_of(&mut iter, &["a", "the"]);
@@ -204,27 +354,13 @@ pub fn decode(s: &str) -> Result<u128, Error> {
         actual: 15,
     })?;
 
-    let mut bits = 0;
-
-    let prepositions = [preposition2, preposition1];
-    let adverbs = [adverb2, adverb1];
-    let verbs = [verb4, verb3, verb2, verb1];
-    let nouns = [noun4, noun3, noun2, noun1];
-    let adjectives = [adjective4, adjective3, adjective2, adjective1];
-
-    bits = Preposition::decode_words(prepositions, bits).map_err(|i| Error::Unrecognized {
-        word: prepositions[i],
-    })?;
-    bits = Adverb::decode_words(adverbs, bits)
-        .map_err(|i| Error::Unrecognized { word: adverbs[i] })?;
-    bits = Verb::<Plural>::decode_words(verbs, bits)
-        .map_err(|i| Error::Unrecognized { word: verbs[i] })?;
-    bits = Noun::decode_words(nouns, bits).map_err(|i| Error::Unrecognized { word: nouns[i] })?;
-    bits = Adjective::decode_words(adjectives, bits).map_err(|i| Error::Unrecognized {
-        word: adjectives[i],
-    })?;
-
-    Ok(bits)
+    Ok(Words {
+        adjectives: [adjective1, adjective2, adjective3, adjective4],
+        nouns: [noun1, noun2, noun3, noun4],
+        verbs: [verb1, verb2, verb3, verb4],
+        adverbs: [adverb1, adverb2],
+        prepositions: [preposition1, preposition2],
+    })
 }
 
 #[cfg(feature = "serde")]
@@ -233,11 +369,8 @@ where
     T: Copy + Into<u128>,
     S: serde::ser::Serializer,
 {
-    use alloc::string::ToString;
-    use serde::Serialize;
-
     let an = encode((*value).into());
-    an.to_string().serialize(serializer)
+    serializer.collect_str(&an)
 }
 
 #[cfg(feature = "serde")]
@@ -246,11 +379,38 @@ where
     u128: Into<T>,
     D: serde::de::Deserializer<'de>,
 {
-    use alloc::borrow::Cow;
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(ComplexPhrase: u128, |id| Ok(super::encode(id)));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::schemes::sample_ids;
+
+    #[test]
+    fn round_trips() {
+        for id in sample_ids::<ComplexPhrase>(1000) {
+            let phrase = encode(id).to_string();
+            assert_eq!(decode(&phrase), Ok(id), "{}", phrase);
+        }
+    }
 
-    let s = <Cow<str> as serde::de::Deserialize>::deserialize(deserializer)?;
-    match decode(&*s) {
-        Err(err) => Err(serde::de::Error::custom(err)),
-        Ok(id) => Ok(id.into()),
+    #[test]
+    fn decodes_legacy_layout() {
+        let phrase = "The proud possum seasons comfortingly minus the attendant lemur \
+                      across the earthworm and zips agelessly \
+                      that the clever pukka monkfish dubs and withholds";
+        assert_eq!(
+            decode_legacy(phrase),
+            Ok(123_456_789_012_345_678_901_234_567_890)
+        );
+        assert_ne!(decode(phrase), decode_legacy(phrase));
     }
 }
@@ -0,0 +1,227 @@
+use core::fmt;
+
+use crate::{
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
+    Hyphenated,
+};
+
+use super::{skip_one_of, string_to_words, Error, Scheme};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompoundPhrase {
+    pub adjective1: &'static str,
+    pub noun1: &'static str,
+    pub noun2: &'static str,
+    pub verb: &'static str,
+    pub adjective2: &'static str,
+    pub noun3: &'static str,
+}
+
+impl CompoundPhrase {
+    /// Encodes bits into `adjective noun and noun verb adjective noun` scheme
+    /// Returns `None` if bits above 48 are set.
+    #[inline]
+    pub fn try_encode(bits: u64) -> Option<Self> {
+        encode(bits)
+    }
+
+    /// Transform to hyphenated.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+}
+
+impl fmt::Display for CompoundPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The {} {} and the {} {} the {} {}",
+            self.adjective1, self.noun1, self.noun2, self.verb, self.adjective2, self.noun3
+        )
+    }
+}
+
+impl Scheme for CompoundPhrase {
+    type Id = u64;
+
+    const BITS: u32 = 48;
+    const WORDS: usize = 6;
+
+    fn encode_with<D: Dictionary>(id: u64) -> Self {
+        let ([adjective1, adjective2], bits) = D::Adjective::encode_words(id.into());
+        let ([noun1, noun2, noun3], bits) = D::Noun::encode_words(bits);
+        let (verb, _) = D::VerbPlural::encode_word(bits);
+
+        CompoundPhrase {
+            adjective1,
+            noun1,
+            noun2,
+            verb,
+            adjective2,
+            noun3,
+        }
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u64, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Adjective, self.adjective1),
+            (WordClass::Noun, self.noun1),
+            (WordClass::Noun, self.noun2),
+            (WordClass::VerbPlural, self.verb),
+            (WordClass::Adjective, self.adjective2),
+            (WordClass::Noun, self.noun3),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
+    }
+}
+
+/// Encodes bits into a compound phrase.
+/// For 48-bit ids.
+/// Returns `None` if bits above 48 are set.
+pub fn encode(bits: u64) -> Option<CompoundPhrase> {
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into a compound phrase.
+/// For 48-bit ids.
+/// Returns `None` if bits above 48 are set.
+/// Uses words from the specified dictionary.
+#[inline]
+pub fn encode_with<D: Dictionary>(bits: u64) -> Option<CompoundPhrase> {
+    CompoundPhrase::try_encode_with::<D>(bits)
+}
+
+/// Decodes a compound phrase.
+/// For 48-bit ids.
+pub fn decode(s: &str) -> Result<u64, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes a compound phrase.
+/// For 48-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u64, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes a compound phrase.
+/// For 48-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u64, Error<'a>> {
+    let mut iter = string_to_words(s);
+
+    skip_one_of(&mut iter, &["a", "the"]);
+
+    let adjective1 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 6,
+        actual: 0,
+    })?;
+    let noun1 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 6,
+        actual: 1,
+    })?;
+
+    skip_one_of(&mut iter, &["and"]);
+    skip_one_of(&mut iter, &["a", "the"]);
+    let noun2 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 6,
+        actual: 2,
+    })?;
+    let verb = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 6,
+        actual: 3,
+    })?;
+
+    skip_one_of(&mut iter, &["a", "the"]);
+    let adjective2 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 6,
+        actual: 4,
+    })?;
+    let noun3 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 6,
+        actual: 5,
+    })?;
+
+    if iter.next().is_some() {
+        return Err(Error::TrailingWords);
+    }
+
+    let mut bits = 0;
+    bits = D::VerbPlural::decode_word(verb, bits, matcher)?;
+    bits = D::Noun::decode_words([noun3, noun2, noun1], bits, matcher)?;
+    bits = D::Adjective::decode_words([adjective2, adjective1], bits, matcher)?;
+
+    Ok(bits as u64)
+}
+
+/// Serialization error for IDs that don't fit into the scheme.
+#[cfg(feature = "serde")]
+const OVERFLOW: &str = "ID doesn't fit into 48 bits";
+
+#[cfg(feature = "serde")]
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<u64>,
+    S: serde::ser::Serializer,
+{
+    match encode((*value).into()) {
+        None => Err(serde::ser::Error::custom(OVERFLOW)),
+        Some(an) => serializer.collect_str(&an),
+    }
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    u64: Into<T>,
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(CompoundPhrase: u64, |id| super::encode(id).ok_or(super::OVERFLOW));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::schemes::sample_ids;
+
+    #[test]
+    fn round_trips() {
+        for id in sample_ids::<CompoundPhrase>(1000) {
+            let phrase = encode(id).unwrap().to_string();
+            assert_eq!(decode(&phrase), Ok(id), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn rejects_wide_ids() {
+        let max = (1 << 48) - 1;
+        assert!(encode(max).is_some());
+        assert_eq!(encode(max + 1), None);
+        assert_eq!(encode(u64::MAX), None);
+        assert_eq!(CompoundPhrase::try_encode(u64::MAX), None);
+    }
+}
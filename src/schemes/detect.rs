@@ -0,0 +1,367 @@
+//!
+//! Decoding of phrases of unknown scheme.
+//!
+//! Schemes differ in number of words and classes of words in the phrase.
+//! [`detect_and_decode`] counts words, ignoring articles and conjunctions,
+//! and tries schemes with that number of words.
+//! Fixed length schemes are preferred when they decode the phrase exactly,
+//! that is encoding the ID back gives the same words.
+//! When none of them decodes the phrase, error is reported for the scheme
+//! whose word classes match words of the phrase best.
+//!
+
+use core::{convert::TryFrom, fmt};
+
+use crate::{
+    dict::{Dictionary, Standard},
+    matcher::Exact,
+};
+
+use super::{
+    id_from_bits, string_to_words, AdjectiveNoun, AdjectiveNounVerb, ComplexPhrase, CompoundPhrase,
+    DoublePhrase, Error, Phrase, PrepositionPronoun, Punk, Scheme, SimplePhrase, VarPhrase,
+    SENTENCE,
+};
+
+/// Words that join words of phrases and are not encoding anything.
+const FILLERS: [&str; 4] = ["a", "the", "and", "that"];
+
+/// Schemes recognized by [`detect_and_decode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SchemeKind {
+    AdjectiveNoun,
+    PrepositionPronoun,
+    AdjectiveNounVerb,
+    SimplePhrase,
+    CompoundPhrase,
+    Phrase,
+    Punk,
+    DoublePhrase,
+    ComplexPhrase,
+    VarPhrase,
+}
+
+impl SchemeKind {
+    /// All recognized schemes, in order they are tried.
+    ///
+    /// `VarPhrase` comes last. It decodes phrases of `AdjectiveNoun`,
+    /// `AdjectiveNounVerb` and `SimplePhrase` into the same IDs,
+    /// but its phrases of two to four words hold more bits,
+    /// so fixed length schemes may decode them only partially.
+    pub const ALL: [SchemeKind; 10] = [
+        SchemeKind::AdjectiveNoun,
+        SchemeKind::PrepositionPronoun,
+        SchemeKind::AdjectiveNounVerb,
+        SchemeKind::SimplePhrase,
+        SchemeKind::CompoundPhrase,
+        SchemeKind::Phrase,
+        SchemeKind::Punk,
+        SchemeKind::DoublePhrase,
+        SchemeKind::ComplexPhrase,
+        SchemeKind::VarPhrase,
+    ];
+
+    /// Returns name of the scheme module, e.g. `"simple_phrase"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            SchemeKind::VarPhrase => "var_phrase",
+            SchemeKind::PrepositionPronoun => "preposition_pronoun",
+            SchemeKind::AdjectiveNoun => "adjective_noun",
+            SchemeKind::AdjectiveNounVerb => "adjective_noun_verb",
+            SchemeKind::SimplePhrase => "simple_phrase",
+            SchemeKind::CompoundPhrase => "compound_phrase",
+            SchemeKind::Phrase => "phrase",
+            SchemeKind::Punk => "punk",
+            SchemeKind::DoublePhrase => "double_phrase",
+            SchemeKind::ComplexPhrase => "complex_phrase",
+        }
+    }
+
+    /// Returns scheme by name of its module.
+    pub fn from_name(name: &str) -> Option<Self> {
+        SchemeKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.name() == name)
+    }
+
+    /// Returns number of bits encoded by the scheme.
+    pub const fn bits(self) -> u32 {
+        match self {
+            SchemeKind::VarPhrase => VarPhrase::BITS,
+            SchemeKind::PrepositionPronoun => PrepositionPronoun::BITS,
+            SchemeKind::AdjectiveNoun => AdjectiveNoun::BITS,
+            SchemeKind::AdjectiveNounVerb => AdjectiveNounVerb::BITS,
+            SchemeKind::SimplePhrase => SimplePhrase::BITS,
+            SchemeKind::CompoundPhrase => CompoundPhrase::BITS,
+            SchemeKind::Phrase => Phrase::BITS,
+            SchemeKind::Punk => Punk::BITS,
+            SchemeKind::DoublePhrase => DoublePhrase::BITS,
+            SchemeKind::ComplexPhrase => ComplexPhrase::BITS,
+        }
+    }
+
+    /// Decodes phrase with this scheme.
+    #[inline]
+    pub fn decode(self, s: &str) -> Result<u128, Error<'_>> {
+        self.decode_with::<Standard>(s)
+    }
+
+    /// Decodes phrase with this scheme.
+    /// Uses words from the specified dictionary.
+    pub fn decode_with<D: Dictionary>(self, s: &str) -> Result<u128, Error<'_>> {
+        match self {
+            SchemeKind::VarPhrase => decode_as::<VarPhrase, D>(s),
+            SchemeKind::PrepositionPronoun => decode_as::<PrepositionPronoun, D>(s),
+            SchemeKind::AdjectiveNoun => decode_as::<AdjectiveNoun, D>(s),
+            SchemeKind::AdjectiveNounVerb => decode_as::<AdjectiveNounVerb, D>(s),
+            SchemeKind::SimplePhrase => decode_as::<SimplePhrase, D>(s),
+            SchemeKind::CompoundPhrase => decode_as::<CompoundPhrase, D>(s),
+            SchemeKind::Phrase => decode_as::<Phrase, D>(s),
+            SchemeKind::Punk => decode_as::<Punk, D>(s),
+            SchemeKind::DoublePhrase => decode_as::<DoublePhrase, D>(s),
+            SchemeKind::ComplexPhrase => decode_as::<ComplexPhrase, D>(s),
+        }
+    }
+
+    /// Returns how many words the scheme's phrases have less or more than `words`.
+    fn words_distance(self, words: usize) -> usize {
+        let (min, max) = match self {
+            SchemeKind::VarPhrase => (2, VarPhrase::WORDS),
+            SchemeKind::PrepositionPronoun => fixed_words::<PrepositionPronoun>(),
+            SchemeKind::AdjectiveNoun => fixed_words::<AdjectiveNoun>(),
+            SchemeKind::AdjectiveNounVerb => fixed_words::<AdjectiveNounVerb>(),
+            SchemeKind::SimplePhrase => fixed_words::<SimplePhrase>(),
+            SchemeKind::CompoundPhrase => fixed_words::<CompoundPhrase>(),
+            SchemeKind::Phrase => fixed_words::<Phrase>(),
+            SchemeKind::Punk => fixed_words::<Punk>(),
+            SchemeKind::DoublePhrase => fixed_words::<DoublePhrase>(),
+            SchemeKind::ComplexPhrase => fixed_words::<ComplexPhrase>(),
+        };
+
+        if words < min {
+            min - words
+        } else {
+            words.saturating_sub(max)
+        }
+    }
+
+    /// Checks if encoding the ID with this scheme gives words of the phrase.
+    fn is_exact<D: Dictionary>(self, s: &str, id: u128) -> bool {
+        match self {
+            SchemeKind::VarPhrase => exact_as::<VarPhrase, D>(s, id),
+            SchemeKind::PrepositionPronoun => exact_as::<PrepositionPronoun, D>(s, id),
+            SchemeKind::AdjectiveNoun => exact_as::<AdjectiveNoun, D>(s, id),
+            SchemeKind::AdjectiveNounVerb => exact_as::<AdjectiveNounVerb, D>(s, id),
+            SchemeKind::SimplePhrase => exact_as::<SimplePhrase, D>(s, id),
+            SchemeKind::CompoundPhrase => exact_as::<CompoundPhrase, D>(s, id),
+            SchemeKind::Phrase => exact_as::<Phrase, D>(s, id),
+            SchemeKind::Punk => exact_as::<Punk, D>(s, id),
+            SchemeKind::DoublePhrase => exact_as::<DoublePhrase, D>(s, id),
+            SchemeKind::ComplexPhrase => exact_as::<ComplexPhrase, D>(s, id),
+        }
+    }
+
+    /// Counts words of the phrase that belong to the class expected at their position.
+    fn score<D: Dictionary>(self, s: &str) -> usize {
+        match self {
+            SchemeKind::VarPhrase => content_words(s)
+                .zip(SENTENCE.iter().cycle())
+                .filter(|&(word, &class)| D::word_mapper(class).find(word).is_some())
+                .count(),
+            SchemeKind::PrepositionPronoun => score_as::<PrepositionPronoun, D>(s),
+            SchemeKind::AdjectiveNoun => score_as::<AdjectiveNoun, D>(s),
+            SchemeKind::AdjectiveNounVerb => score_as::<AdjectiveNounVerb, D>(s),
+            SchemeKind::SimplePhrase => score_as::<SimplePhrase, D>(s),
+            SchemeKind::CompoundPhrase => score_as::<CompoundPhrase, D>(s),
+            SchemeKind::Phrase => score_as::<Phrase, D>(s),
+            SchemeKind::Punk => score_as::<Punk, D>(s),
+            SchemeKind::DoublePhrase => score_as::<DoublePhrase, D>(s),
+            SchemeKind::ComplexPhrase => score_as::<ComplexPhrase, D>(s),
+        }
+    }
+}
+
+impl fmt::Display for SchemeKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error of [`detect_and_decode`].
+/// Holds error of the scheme that matches the phrase best.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DetectError<'a> {
+    pub scheme: SchemeKind,
+    pub error: Error<'a>,
+}
+
+impl fmt::Display for DetectError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (as {})", self.error, self.scheme)
+    }
+}
+
+/// Decodes phrase of any scheme listed in [`SchemeKind`].
+/// Returns the scheme that decoded the phrase and the ID.
+///
+/// ```
+/// use meme_id::{detect_and_decode, Scheme, SchemeKind, SimplePhrase};
+///
+/// let phrase = SimplePhrase::encode(42).to_string();
+/// assert_eq!(detect_and_decode(&phrase), Ok((SchemeKind::SimplePhrase, 42)));
+/// ```
+pub fn detect_and_decode(s: &str) -> Result<(SchemeKind, u128), DetectError<'_>> {
+    detect_and_decode_with::<Standard>(s)
+}
+
+/// Decodes phrase of any scheme listed in [`SchemeKind`].
+/// Returns the scheme that decoded the phrase and the ID.
+/// Uses words from the specified dictionary.
+pub fn detect_and_decode_with<D: Dictionary>(
+    s: &str,
+) -> Result<(SchemeKind, u128), DetectError<'_>> {
+    let words = content_words(s).count();
+
+    let mut inexact = None;
+    let mut best: Option<(usize, DetectError<'_>)> = None;
+    for &scheme in &SchemeKind::ALL {
+        if scheme.words_distance(words) != 0 {
+            continue;
+        }
+
+        match scheme.decode_with::<D>(s) {
+            Ok(id) if scheme.is_exact::<D>(s, id) => return Ok((scheme, id)),
+            Ok(id) => {
+                if inexact.is_none() {
+                    inexact = Some((scheme, id));
+                }
+            }
+            Err(error) => {
+                let score = scheme.score::<D>(s);
+                match best {
+                    Some((best, _)) if best >= score => {}
+                    _ => best = Some((score, DetectError { scheme, error })),
+                }
+            }
+        }
+    }
+
+    // Words of some classes are picked from subsets of the dictionary
+    // and decoding may accept words outside of them.
+    if let Some(decoded) = inexact {
+        return Ok(decoded);
+    }
+
+    if let Some((_, err)) = best {
+        return Err(err);
+    }
+
+    // No scheme has phrases with this number of words.
+    // Report the one with closest number of words.
+    let scheme = SchemeKind::ALL
+        .iter()
+        .copied()
+        .min_by_key(|scheme| scheme.words_distance(words))
+        .unwrap();
+
+    match scheme.decode_with::<D>(s) {
+        Ok(id) => Ok((scheme, id)),
+        Err(error) => Err(DetectError { scheme, error }),
+    }
+}
+
+fn decode_as<S: Scheme, D: Dictionary>(s: &str) -> Result<u128, Error<'_>> {
+    S::decode_by::<D>(s, &mut Exact).map(Into::into)
+}
+
+fn fixed_words<S: Scheme>() -> (usize, usize) {
+    (S::WORDS, S::WORDS)
+}
+
+fn exact_as<S: Scheme, D: Dictionary>(s: &str, id: u128) -> bool {
+    let id = match S::Id::try_from(id) {
+        Ok(id) => id,
+        Err(_) => return false,
+    };
+
+    let mut words = content_words(s);
+    let same = S::encode_with::<D>(id).for_each_word(|_, expected| match words.next() {
+        Some(word) if word.eq_ignore_ascii_case(expected) => Ok(()),
+        _ => Err(()),
+    });
+
+    same.is_ok() && words.next().is_none()
+}
+
+fn score_as<S: Scheme, D: Dictionary>(s: &str) -> usize {
+    let mut words = content_words(s);
+    let mut score = 0;
+
+    let phrase = S::encode_with::<D>(id_from_bits::<S>(0));
+    let _ = phrase.for_each_word(|class, _| {
+        match words.next() {
+            Some(word) if D::word_mapper(class).find(word).is_some() => score += 1,
+            Some(_) => {}
+            None => return Err(()),
+        }
+        Ok(())
+    });
+
+    score
+}
+
+/// Returns words of the phrase except fillers.
+fn content_words(s: &str) -> impl Iterator<Item = &str> {
+    string_to_words(s).filter(|word| !FILLERS.iter().any(|f| word.eq_ignore_ascii_case(f)))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::schemes::sample_ids;
+
+    fn check<S: Scheme>(kind: SchemeKind) {
+        for id in sample_ids::<S>(1000) {
+            let phrase = S::encode(id).to_string();
+            assert_eq!(
+                detect_and_decode(&phrase),
+                Ok((kind, id.into())),
+                "{}",
+                phrase
+            );
+        }
+    }
+
+    #[test]
+    fn detects_fixed_length_schemes() {
+        check::<PrepositionPronoun>(SchemeKind::PrepositionPronoun);
+        check::<AdjectiveNoun>(SchemeKind::AdjectiveNoun);
+        check::<AdjectiveNounVerb>(SchemeKind::AdjectiveNounVerb);
+        check::<SimplePhrase>(SchemeKind::SimplePhrase);
+        check::<CompoundPhrase>(SchemeKind::CompoundPhrase);
+        check::<Phrase>(SchemeKind::Phrase);
+        check::<Punk>(SchemeKind::Punk);
+        check::<DoublePhrase>(SchemeKind::DoublePhrase);
+        check::<ComplexPhrase>(SchemeKind::ComplexPhrase);
+    }
+
+    #[test]
+    fn decodes_var_phrases_beyond_fixed_length_schemes() {
+        for &id in &[0x1_e240, 0xff_ffff_u128 << 8, u128::from(u32::MAX) << 4] {
+            let phrase = VarPhrase::encode(id).to_string();
+            assert_eq!(detect_and_decode(&phrase).map(|(_, id)| id), Ok(id));
+        }
+    }
+
+    #[test]
+    fn blames_fixed_length_scheme_on_ties() {
+        let err = detect_and_decode("fluffy prud").unwrap_err();
+        assert_eq!(err.scheme, SchemeKind::AdjectiveNoun);
+    }
+}
@@ -0,0 +1,290 @@
+use core::fmt;
+
+use crate::{
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
+    Hyphenated,
+};
+
+use super::{skip_one_of, string_to_words, Error, Scheme};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DoublePhrase {
+    pub adjective1: &'static str,
+    pub noun1: &'static str,
+    pub verb1: &'static str,
+    pub preposition1: &'static str,
+    pub adjective2: &'static str,
+    pub noun2: &'static str,
+    pub adjective3: &'static str,
+    pub noun3: &'static str,
+    pub verb2: &'static str,
+    pub preposition2: &'static str,
+    pub adjective4: &'static str,
+    pub noun4: &'static str,
+}
+
+impl DoublePhrase {
+    /// Encodes bits into two `adjective noun verb preposition adjective noun` clauses
+    /// Returns `None` if bits above 96 are set.
+    #[inline]
+    pub fn try_encode(bits: u128) -> Option<Self> {
+        encode(bits)
+    }
+
+    /// Transform to hyphenated.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+}
+
+impl fmt::Display for DoublePhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The {} {} {} {} the {} {} and the {} {} {} {} the {} {}",
+            self.adjective1,
+            self.noun1,
+            self.verb1,
+            self.preposition1,
+            self.adjective2,
+            self.noun2,
+            self.adjective3,
+            self.noun3,
+            self.verb2,
+            self.preposition2,
+            self.adjective4,
+            self.noun4
+        )
+    }
+}
+
+impl Scheme for DoublePhrase {
+    type Id = u128;
+
+    const BITS: u32 = 96;
+    const WORDS: usize = 12;
+
+    fn encode_with<D: Dictionary>(id: u128) -> Self {
+        let ([adjective1, adjective2], bits) = D::Adjective::encode_words(id);
+        let ([adjective3, adjective4], bits) = D::Adjective::encode_words(bits);
+        let ([noun1, noun2], bits) = D::Noun::encode_words(bits);
+        let ([noun3, noun4], bits) = D::Noun::encode_words(bits);
+        let ([verb1, verb2], bits) = D::VerbPlural::encode_words(bits);
+        let (preposition1, bits) = D::Preposition::encode_word(bits);
+        let (preposition2, _) = D::Preposition::encode_word(bits);
+
+        DoublePhrase {
+            adjective1,
+            noun1,
+            verb1,
+            preposition1,
+            adjective2,
+            noun2,
+            adjective3,
+            noun3,
+            verb2,
+            preposition2,
+            adjective4,
+            noun4,
+        }
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u128, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Adjective, self.adjective1),
+            (WordClass::Noun, self.noun1),
+            (WordClass::VerbPlural, self.verb1),
+            (WordClass::Preposition, self.preposition1),
+            (WordClass::Adjective, self.adjective2),
+            (WordClass::Noun, self.noun2),
+            (WordClass::Adjective, self.adjective3),
+            (WordClass::Noun, self.noun3),
+            (WordClass::VerbPlural, self.verb2),
+            (WordClass::Preposition, self.preposition2),
+            (WordClass::Adjective, self.adjective4),
+            (WordClass::Noun, self.noun4),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
+    }
+}
+
+/// Encodes bits into a double phrase.
+/// For 96-bit ids.
+/// Returns `None` if bits above 96 are set.
+pub fn encode(bits: u128) -> Option<DoublePhrase> {
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into a double phrase.
+/// For 96-bit ids.
+/// Returns `None` if bits above 96 are set.
+/// Uses words from the specified dictionary.
+#[inline]
+pub fn encode_with<D: Dictionary>(bits: u128) -> Option<DoublePhrase> {
+    DoublePhrase::try_encode_with::<D>(bits)
+}
+
+/// Decodes a double phrase.
+/// For 96-bit ids.
+pub fn decode(s: &str) -> Result<u128, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes a double phrase.
+/// For 96-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u128, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes a double phrase.
+/// For 96-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u128, Error<'a>> {
+    let mut iter = string_to_words(s);
+
+    skip_one_of(&mut iter, &["a", "the"]);
+
+    let adjective1 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 0,
+    })?;
+    let noun1 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 1,
+    })?;
+    let verb1 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 2,
+    })?;
+    let preposition1 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 3,
+    })?;
+
+    skip_one_of(&mut iter, &["a", "the"]);
+    let adjective2 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 4,
+    })?;
+    let noun2 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 5,
+    })?;
+
+    skip_one_of(&mut iter, &["and"]);
+    skip_one_of(&mut iter, &["a", "the"]);
+    let adjective3 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 6,
+    })?;
+    let noun3 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 7,
+    })?;
+    let verb2 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 8,
+    })?;
+    let preposition2 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 9,
+    })?;
+
+    skip_one_of(&mut iter, &["a", "the"]);
+    let adjective4 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 10,
+    })?;
+    let noun4 = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 12,
+        actual: 11,
+    })?;
+
+    if iter.next().is_some() {
+        return Err(Error::TrailingWords);
+    }
+
+    let mut bits = 0;
+
+    bits = D::Preposition::decode_word(preposition2, bits, matcher)?;
+    bits = D::Preposition::decode_word(preposition1, bits, matcher)?;
+    bits = D::VerbPlural::decode_words([verb2, verb1], bits, matcher)?;
+    bits = D::Noun::decode_words([noun4, noun3], bits, matcher)?;
+    bits = D::Noun::decode_words([noun2, noun1], bits, matcher)?;
+    bits = D::Adjective::decode_words([adjective4, adjective3], bits, matcher)?;
+    bits = D::Adjective::decode_words([adjective2, adjective1], bits, matcher)?;
+
+    Ok(bits)
+}
+
+/// Serialization error for IDs that don't fit into the scheme.
+#[cfg(feature = "serde")]
+const OVERFLOW: &str = "ID doesn't fit into 96 bits";
+
+#[cfg(feature = "serde")]
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<u128>,
+    S: serde::ser::Serializer,
+{
+    match encode((*value).into()) {
+        None => Err(serde::ser::Error::custom(OVERFLOW)),
+        Some(an) => serializer.collect_str(&an),
+    }
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    u128: Into<T>,
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(DoublePhrase: u128, |id| super::encode(id).ok_or(super::OVERFLOW));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::schemes::sample_ids;
+
+    #[test]
+    fn round_trips() {
+        for id in sample_ids::<DoublePhrase>(1000) {
+            let phrase = encode(id).unwrap().to_string();
+            assert_eq!(decode(&phrase), Ok(id), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn rejects_wide_ids() {
+        let max = (1 << 96) - 1;
+        assert!(encode(max).is_some());
+        assert_eq!(encode(max + 1), None);
+        assert_eq!(encode(u128::MAX), None);
+    }
+}
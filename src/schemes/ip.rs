@@ -0,0 +1,143 @@
+//!
+//! Shared implementation of `ipv4` and `ipv6` modules.
+//!
+
+/// Generates functions that encode IP addresses of `core::net::$addr` type
+/// through `$bits` integers into phrases of `$scheme` module,
+/// and serde `with`-modules for them.
+macro_rules! addr_module {
+    ($addr:ident: $bits:ty => $scheme:ident::$phrase:ident, $name:literal, $kind:literal) => {
+        use core::net::$addr;
+
+        use $crate::{
+            dict::{Dictionary, Standard},
+            matcher::{Exact, Matcher},
+            schemes::{$phrase, $scheme, Error},
+        };
+
+        #[doc = concat!("Encodes ", $name, " address into ", $kind, ".")]
+        pub fn encode(addr: $addr) -> $phrase {
+            encode_with::<Standard>(addr)
+        }
+
+        #[doc = concat!("Encodes ", $name, " address into ", $kind, ".")]
+        /// Uses words from the specified dictionary.
+        pub fn encode_with<D: Dictionary>(addr: $addr) -> $phrase {
+            $scheme::encode_with::<D>(<$bits>::from(addr))
+        }
+
+        #[doc = concat!("Decodes ", $name, " address from ", $kind, ".")]
+        pub fn decode(s: &str) -> Result<$addr, Error<'_>> {
+            decode_with::<Standard>(s)
+        }
+
+        #[doc = concat!("Decodes ", $name, " address from ", $kind, ".")]
+        /// Uses words from the specified dictionary.
+        pub fn decode_with<D: Dictionary>(s: &str) -> Result<$addr, Error<'_>> {
+            decode_by::<D>(s, &mut Exact)
+        }
+
+        #[doc = concat!("Decodes ", $name, " address from ", $kind, ".")]
+        /// Uses words from the specified dictionary matched by the matcher.
+        pub fn decode_by<'a, D: Dictionary>(
+            s: &'a str,
+            matcher: &mut impl Matcher,
+        ) -> Result<$addr, Error<'a>> {
+            $scheme::decode_by::<D>(s, matcher).map($addr::from)
+        }
+
+        #[cfg(feature = "serde")]
+        pub fn serialize<S>(value: &$addr, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::ser::Serializer,
+        {
+            let an = encode(*value);
+            serializer.collect_str(&an)
+        }
+
+        #[cfg(feature = "serde")]
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<$addr, D::Error>
+        where
+            D: serde::de::Deserializer<'de>,
+        {
+            $crate::schemes::deserialize_phrase(deserializer, decode)
+        }
+
+        #[cfg(feature = "serde")]
+        $crate::schemes::with::modules!(core::net::$addr, |id| Ok(super::encode(id)));
+    };
+}
+
+pub(super) use addr_module;
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::net::{Ipv4Addr, Ipv6Addr};
+    use std::string::ToString;
+
+    use crate::schemes::{complex_phrase, ipv4, ipv6, simple_phrase};
+
+    const IPV4: [Ipv4Addr; 4] = [
+        Ipv4Addr::UNSPECIFIED,
+        Ipv4Addr::BROADCAST,
+        Ipv4Addr::LOCALHOST,
+        Ipv4Addr::new(192, 168, 1, 42),
+    ];
+
+    const IPV6: [Ipv6Addr; 5] = [
+        Ipv6Addr::UNSPECIFIED,
+        Ipv6Addr::LOCALHOST,
+        Ipv6Addr::new(0xffff, 0, 0, 0, 0, 0, 0, 0),
+        Ipv6Addr::new(
+            0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+        ),
+        Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0xff00, 0x42, 0x8329),
+    ];
+
+    #[test]
+    fn ipv4_round_trips() {
+        for &addr in &IPV4 {
+            let phrase = ipv4::encode(addr).to_string();
+            assert_eq!(ipv4::decode(&phrase), Ok(addr), "{}", phrase);
+        }
+        assert_eq!(
+            ipv4::encode(Ipv4Addr::new(1, 2, 3, 4)),
+            simple_phrase::encode(0x0102_0304)
+        );
+    }
+
+    #[test]
+    fn ipv6_round_trips() {
+        for &addr in &IPV6 {
+            let phrase = ipv6::encode(addr).to_string();
+            assert_eq!(ipv6::decode(&phrase), Ok(addr), "{}", phrase);
+        }
+        assert_eq!(ipv6::encode(Ipv6Addr::LOCALHOST), complex_phrase::encode(1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips() {
+        use std::vec::Vec;
+
+        use crate::schemes::json::{from_json, to_json};
+
+        for &addr in &IPV4 {
+            let json = to_json(|s| ipv4::serialize(&addr, s));
+            assert_eq!(json, std::format!("\"{}\"", ipv4::encode(addr)));
+            assert_eq!(from_json(&json, |d| ipv4::deserialize(d)).unwrap(), addr);
+        }
+
+        let json = to_json(|s| ipv4::option::serialize(&Some(IPV4[1]), s));
+        assert_eq!(
+            from_json(&json, |d| ipv4::option::deserialize(d)).unwrap(),
+            Some(IPV4[1])
+        );
+
+        let json = to_json(|s| ipv6::seq::serialize(&IPV6, s));
+        let addrs: Vec<Ipv6Addr> = from_json(&json, |d| ipv6::seq::deserialize(d)).unwrap();
+        assert_eq!(addrs, IPV6);
+    }
+}
@@ -0,0 +1,7 @@
+//!
+//! Encoding of IPv4 addresses into simple phrases.
+//!
+//! Can be used as serde `with`-module for `Ipv4Addr` fields.
+//!
+
+super::ip::addr_module!(Ipv4Addr: u32 => simple_phrase::SimplePhrase, "IPv4", "a simple phrase");
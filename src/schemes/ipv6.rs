@@ -0,0 +1,7 @@
+//!
+//! Encoding of IPv6 addresses into complex phrases.
+//!
+//! Can be used as serde `with`-module for `Ipv6Addr` fields.
+//!
+
+super::ip::addr_module!(Ipv6Addr: u128 => complex_phrase::ComplexPhrase, "IPv6", "a complex phrase");
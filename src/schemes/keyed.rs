@@ -0,0 +1,299 @@
+use core::{fmt, hash, marker::PhantomData};
+
+use crate::{
+    dict::{Dictionary, WordClass},
+    matcher::Matcher,
+};
+
+use super::{id_from_bits, Error, Scheme};
+
+/// Secret key for [`Keyed`] scheme.
+pub trait Key {
+    /// Returns the secret key.
+    fn key() -> u128;
+}
+
+/// Keyed bijective permutation of IDs.
+///
+/// Uses Feistel network with a mixing round function.
+/// Hides sequence of IDs from observers without the key,
+/// but is not designed to withstand cryptanalysis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Feistel {
+    round_keys: [u64; ROUNDS],
+}
+
+/// Number of Feistel rounds. Must be even.
+const ROUNDS: usize = 8;
+
+impl Feistel {
+    /// Returns permutation derived from the key.
+    pub const fn new(key: u128) -> Self {
+        let mut state = (key as u64) ^ mix((key >> 64) as u64);
+        let mut round_keys = [0; ROUNDS];
+
+        let mut i = 0;
+        while i < ROUNDS {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            round_keys[i] = mix(state);
+            i += 1;
+        }
+
+        Feistel { round_keys }
+    }
+
+    /// Permutes lower `bits` bits of the value.
+    /// Value must not have higher bits set.
+    pub fn permute(&self, bits: u32, mut value: u128) -> u128 {
+        debug_assert!(bits > 127 || value >> bits == 0);
+
+        if bits < 2 {
+            return value;
+        }
+
+        let (mut hi_bits, mut lo_bits) = (bits - bits / 2, bits / 2);
+        for &key in &self.round_keys {
+            let lo = value & mask(lo_bits);
+            let hi = (value >> lo_bits) ^ (round(lo, key) & mask(hi_bits));
+            value = (lo << hi_bits) | hi;
+            core::mem::swap(&mut hi_bits, &mut lo_bits);
+        }
+
+        value
+    }
+
+    /// Reverses [`Feistel::permute`].
+    pub fn unpermute(&self, bits: u32, mut value: u128) -> u128 {
+        debug_assert!(bits > 127 || value >> bits == 0);
+
+        if bits < 2 {
+            return value;
+        }
+
+        let (mut hi_bits, mut lo_bits) = (bits - bits / 2, bits / 2);
+        for &key in self.round_keys.iter().rev() {
+            let lo = value >> lo_bits;
+            let hi = (value & mask(lo_bits)) ^ (round(lo, key) & mask(lo_bits));
+            value = (hi << hi_bits) | lo;
+            core::mem::swap(&mut hi_bits, &mut lo_bits);
+        }
+
+        value
+    }
+}
+
+/// Returns mask for lower `bits` bits. `bits` must be in `1..=64`.
+#[inline]
+fn mask(bits: u32) -> u128 {
+    u128::MAX >> (128 - bits)
+}
+
+#[inline]
+fn round(half: u128, key: u64) -> u128 {
+    mix(half as u64 ^ key) as u128
+}
+
+/// Finalizer of SplitMix64.
+const fn mix(mut x: u64) -> u64 {
+    x ^= x >> 30;
+    x = x.wrapping_mul(0xbf58476d1ce4e5b9);
+    x ^= x >> 27;
+    x = x.wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+/// Phrase of the scheme that encodes ID permuted with secret key.
+///
+/// Sequential IDs produce unrelated phrases,
+/// while phrases can be decoded back with the same key.
+///
+/// ```
+/// use meme_id::{Key, Keyed, Scheme, SimplePhrase};
+///
+/// enum Secret {}
+///
+/// impl Key for Secret {
+///     fn key() -> u128 {
+///         0x1badb002_8badf00d_deadbeef_c0ffee42
+///     }
+/// }
+///
+/// type PublicId = Keyed<SimplePhrase, Secret>;
+///
+/// let phrase = PublicId::encode(1).to_string();
+/// assert_eq!(PublicId::decode(&phrase), Ok(1));
+/// ```
+pub struct Keyed<S, K> {
+    pub phrase: S,
+    key: PhantomData<fn() -> K>,
+}
+
+impl<S, K> Keyed<S, K> {
+    /// Wraps phrase that encodes permuted ID.
+    #[inline]
+    pub fn new(phrase: S) -> Self {
+        Keyed {
+            phrase,
+            key: PhantomData,
+        }
+    }
+}
+
+impl<S, K> Clone for Keyed<S, K>
+where
+    S: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Keyed::new(self.phrase.clone())
+    }
+}
+
+impl<S, K> Copy for Keyed<S, K> where S: Copy {}
+
+impl<S, K> fmt::Debug for Keyed<S, K>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Keyed").field(&self.phrase).finish()
+    }
+}
+
+impl<S, K> PartialEq for Keyed<S, K>
+where
+    S: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.phrase == other.phrase
+    }
+}
+
+impl<S, K> Eq for Keyed<S, K> where S: Eq {}
+
+impl<S, K> hash::Hash for Keyed<S, K>
+where
+    S: hash::Hash,
+{
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.phrase.hash(state)
+    }
+}
+
+impl<S, K> fmt::Display for Keyed<S, K>
+where
+    S: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.phrase.fmt(f)
+    }
+}
+
+impl<S, K> Scheme for Keyed<S, K>
+where
+    S: Scheme,
+    K: Key,
+{
+    type Id = S::Id;
+
+    const BITS: u32 = S::BITS;
+    const WORDS: usize = S::WORDS;
+
+    fn encode_with<D: Dictionary>(id: S::Id) -> Self {
+        // Higher bits are ignored as by other schemes.
+        let bits = match S::BITS {
+            128 => id.into(),
+            n => id.into() & !(u128::MAX << n),
+        };
+        let bits = Feistel::new(K::key()).permute(S::BITS, bits);
+        Keyed::new(S::encode_with::<D>(id_from_bits::<S>(bits)))
+    }
+
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<S::Id, Error<'a>> {
+        let id = S::decode_by::<D>(s, matcher)?;
+        let bits = Feistel::new(K::key()).unpermute(S::BITS, id.into());
+        Ok(id_from_bits::<S>(bits))
+    }
+
+    #[inline]
+    fn for_each_word<E>(
+        &self,
+        f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.phrase.for_each_word(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{string::ToString, vec};
+
+    use super::*;
+    use crate::schemes::{sample_ids, ComplexPhrase, CompoundPhrase, SimplePhrase};
+
+    const KEY: u128 = 0x1bad_b002_8bad_f00d_dead_beef_c0ff_ee42;
+
+    enum Secret {}
+
+    impl Key for Secret {
+        fn key() -> u128 {
+            KEY
+        }
+    }
+
+    #[test]
+    fn unpermute_reverses_permute() {
+        let feistel = Feistel::new(KEY);
+        for bits in 1..=128 {
+            for value in sample_ids::<ComplexPhrase>(100) {
+                let value = match bits {
+                    128 => value,
+                    n => value & !(u128::MAX << n),
+                };
+                let permuted = feistel.permute(bits, value);
+                assert!(bits == 128 || permuted >> bits == 0, "{} bits", bits);
+                assert_eq!(feistel.unpermute(bits, permuted), value, "{} bits", bits);
+            }
+        }
+    }
+
+    #[test]
+    fn permute_is_bijection() {
+        let feistel = Feistel::new(KEY);
+        for bits in 1..=12 {
+            let mut seen = vec![false; 1 << bits];
+            for value in 0..1u128 << bits {
+                let permuted = feistel.permute(bits, value) as usize;
+                assert!(!seen[permuted], "{} bits, {}", bits, value);
+                seen[permuted] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn sequential_ids_differ_in_leading_word() {
+        type PublicId = Keyed<SimplePhrase, Secret>;
+
+        let leading = |id| PublicId::encode(id).phrase.adjective;
+        for id in 0..100 {
+            assert_ne!(leading(id), leading(id + 1), "{}", id);
+        }
+    }
+
+    #[test]
+    fn higher_bits_are_ignored() {
+        type PublicId = Keyed<CompoundPhrase, Secret>;
+
+        assert_eq!(PublicId::encode(u64::MAX), PublicId::encode(u64::MAX >> 16));
+        let phrase = PublicId::encode(u64::MAX).to_string();
+        assert_eq!(PublicId::decode(&phrase), Ok(u64::MAX >> 16));
+    }
+}
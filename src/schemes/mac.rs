@@ -0,0 +1,134 @@
+//!
+//! Encoding of 48-bit MAC addresses into compound phrases.
+//!
+//! Addresses are represented as arrays of 6 octets in transmission order.
+//! Can be used as serde `with`-module for `[u8; 6]` fields.
+//!
+
+use crate::{
+    dict::{Dictionary, Standard},
+    matcher::{Exact, Matcher},
+};
+
+use super::{compound_phrase, CompoundPhrase, Error, Scheme};
+
+/// Returns bits of the address with the first octet as the most significant.
+fn to_bits(addr: [u8; 6]) -> u64 {
+    let [a, b, c, d, e, f] = addr;
+    u64::from_be_bytes([0, 0, a, b, c, d, e, f])
+}
+
+/// Reverses [`to_bits`].
+fn from_bits(bits: u64) -> [u8; 6] {
+    let [_, _, a, b, c, d, e, f] = bits.to_be_bytes();
+    [a, b, c, d, e, f]
+}
+
+/// Encodes MAC address into a compound phrase.
+pub fn encode(addr: [u8; 6]) -> CompoundPhrase {
+    encode_with::<Standard>(addr)
+}
+
+/// Encodes MAC address into a compound phrase.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(addr: [u8; 6]) -> CompoundPhrase {
+    CompoundPhrase::encode_with::<D>(to_bits(addr))
+}
+
+/// Decodes MAC address from a compound phrase.
+pub fn decode(s: &str) -> Result<[u8; 6], Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes MAC address from a compound phrase.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<[u8; 6], Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes MAC address from a compound phrase.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<[u8; 6], Error<'a>> {
+    compound_phrase::decode_by::<D>(s, matcher).map(from_bits)
+}
+
+#[cfg(feature = "serde")]
+pub fn serialize<S>(value: &[u8; 6], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    let an = encode(*value);
+    serializer.collect_str(&an)
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 6], D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, decode)
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!([u8; 6], |id| Ok(super::encode(id)));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+
+    const ADDRS: [[u8; 6]; 4] = [
+        [0; 6],
+        [0xff; 6],
+        [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6],
+        [0x01, 0x00, 0x5e, 0x00, 0x00, 0xfb],
+    ];
+
+    #[test]
+    fn round_trips() {
+        for &addr in &ADDRS {
+            let phrase = encode(addr).to_string();
+            assert_eq!(decode(&phrase), Ok(addr), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn first_octet_is_most_significant() {
+        assert_eq!(
+            encode([0, 0, 0, 0, 0, 42]),
+            CompoundPhrase::encode_with::<Standard>(42)
+        );
+        assert_eq!(
+            encode([0x80, 0, 0, 0, 0, 0]),
+            CompoundPhrase::encode_with::<Standard>(1 << 47)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips() {
+        use std::{collections::BTreeMap, vec::Vec};
+
+        use crate::schemes::json::{from_json, to_json};
+
+        for &addr in &ADDRS {
+            let json = to_json(|s| serialize(&addr, s));
+            assert_eq!(from_json(&json, |d| deserialize(d)).unwrap(), addr);
+        }
+
+        let json = to_json(|s| seq::serialize(&ADDRS, s));
+        let addrs: Vec<[u8; 6]> = from_json(&json, |d| seq::deserialize(d)).unwrap();
+        assert_eq!(addrs, ADDRS);
+
+        let map: BTreeMap<[u8; 6], u32> = ADDRS.iter().copied().zip(0..).collect();
+        let json = to_json(|s| keys::serialize(&map, s));
+        let decoded: BTreeMap<[u8; 6], u32> = from_json(&json, |d| keys::deserialize(d)).unwrap();
+        assert_eq!(decoded, map);
+    }
+}
@@ -1,16 +1,98 @@
 pub mod adjective_noun;
+pub mod adjective_noun_verb;
+pub mod bytes;
+mod checked;
 pub mod complex_phrase;
+pub mod compound_phrase;
+mod detect;
+pub mod double_phrase;
+mod ip;
+pub mod ipv4;
+pub mod ipv6;
+mod keyed;
+pub mod mac;
 pub mod phrase;
+pub mod preposition_pronoun;
 pub mod punk;
 pub mod simple_phrase;
+#[cfg(feature = "snowflake")]
+pub mod snowflake;
+#[cfg(feature = "ulid")]
+pub mod ulid;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+#[cfg(feature = "uuid")]
+pub mod uuid_v4;
+pub mod var_phrase;
+#[cfg(feature = "serde")]
+mod with;
 
-use core::{fmt, iter::Peekable};
+pub use self::{
+    adjective_noun::AdjectiveNoun,
+    adjective_noun_verb::AdjectiveNounVerb,
+    bytes::Bytes,
+    checked::Checked,
+    complex_phrase::ComplexPhrase,
+    compound_phrase::CompoundPhrase,
+    detect::{detect_and_decode, detect_and_decode_with, DetectError, SchemeKind},
+    double_phrase::DoublePhrase,
+    keyed::{Feistel, Key, Keyed},
+    phrase::Phrase,
+    preposition_pronoun::PrepositionPronoun,
+    punk::Punk,
+    simple_phrase::SimplePhrase,
+    var_phrase::VarPhrase,
+};
+
+#[cfg(feature = "snowflake")]
+pub use self::snowflake::SnowflakePhrase;
+#[cfg(feature = "ulid")]
+pub use self::ulid::UlidPhrase;
+#[cfg(feature = "uuid")]
+pub use self::uuid_v4::UuidV4Phrase;
+
+use core::{convert::TryFrom, fmt, iter::Peekable, marker::PhantomData};
+
+use crate::{
+    dict::{Dictionary, Standard, WordClass},
+    matcher::{Confidence, Exact, Fuzzy, Matcher, Prefix},
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Error<'a> {
     NotEnoughWords { expected: usize, actual: usize },
     TrailingWords,
-    Unrecognized { word: &'a str },
+    Unrecognized { word: &'a str, class: WordClass },
+    Ambiguous { word: &'a str, class: WordClass },
+    ChecksumMismatch,
+    InvalidLength,
+    Overflow,
+    BufferTooSmall { required: usize },
+}
+
+impl Error<'_> {
+    /// Fills `out` with dictionary words closest to the unrecognized or ambiguous word,
+    /// closest first.
+    /// Returns filled part of `out`, which is empty for other errors.
+    #[inline]
+    pub fn suggestions<'b>(&self, out: &'b mut [&'static str]) -> &'b [&'static str] {
+        self.suggestions_with::<Standard>(out)
+    }
+
+    /// Fills `out` with words from the dictionary closest to the unrecognized or ambiguous word,
+    /// closest first.
+    /// Returns filled part of `out`, which is empty for other errors.
+    pub fn suggestions_with<'b, D: Dictionary>(
+        &self,
+        out: &'b mut [&'static str],
+    ) -> &'b [&'static str] {
+        match *self {
+            Error::Unrecognized { word, class } | Error::Ambiguous { word, class } => {
+                D::word_mapper(class).find_nearest_n(word, out)
+            }
+            _ => &out[..0],
+        }
+    }
 }
 impl fmt::Display for Error<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -25,13 +107,42 @@ impl fmt::Display for Error<'_> {
             Error::TrailingWords => {
                 write!(f, "Words left after parsing")
             }
-            Error::Unrecognized { word } => {
-                write!(f, "Word '{}' unrecognized", word)
+            Error::Unrecognized { word, class } => {
+                write!(f, "Word '{}' unrecognized, expected {}", word, class)
+            }
+            Error::Ambiguous { word, class } => {
+                write!(f, "Word '{}' is ambiguous, expected {}", word, class)
+            }
+            Error::ChecksumMismatch => {
+                write!(f, "Checksum word doesn't match")
+            }
+            Error::InvalidLength => {
+                write!(f, "Words don't encode whole number of bytes")
+            }
+            Error::Overflow => {
+                write!(f, "Words encode number too large")
+            }
+            Error::BufferTooSmall { required } => {
+                write!(f, "Output buffer is too small. Required {} bytes", required)
             }
         }
     }
 }
 
+/// Classes of words in a sentence of variable length phrases.
+const SENTENCE: [WordClass; 7] = [
+    WordClass::Adjective,
+    WordClass::Noun,
+    WordClass::VerbPlural,
+    WordClass::Adverb,
+    WordClass::Preposition,
+    WordClass::Adjective,
+    WordClass::Noun,
+];
+
+/// Positions in a sentence that may be preceded by an article.
+const ARTICLES: [usize; 2] = [0, 5];
+
 fn skip_one_of<'a>(iter: &mut Peekable<impl Iterator<Item = &'a str>>, skip: &[&str]) {
     iter.next_if(|word| {
         skip.iter()
@@ -39,13 +150,512 @@ fn skip_one_of<'a>(iter: &mut Peekable<impl Iterator<Item = &'a str>>, skip: &[&
     });
 }
 
-fn string_to_words<'a>(s: &'a str) -> Peekable<impl Iterator<Item = &'a str>> {
+fn string_to_words(s: &str) -> Peekable<impl Iterator<Item = &str>> {
     s.split(|ch: char| !ch.is_ascii_alphabetic())
         .filter(|s| !s.is_empty() && !s.contains(|ch: char| !ch.is_ascii_alphabetic()))
         .peekable()
 }
 
+/// Splits off the last word of the string.
+/// Returns the rest of the string and the word.
+fn split_last_word(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_end_matches(|ch: char| !ch.is_ascii_alphabetic());
+    let start = s
+        .trim_end_matches(|ch: char| ch.is_ascii_alphabetic())
+        .len();
+    if start == s.len() {
+        None
+    } else {
+        Some((&s[..start], &s[start..]))
+    }
+}
+
+/// Strips the last word of the string if it is one of `skip`.
+fn strip_last_one_of<'a>(s: &'a str, skip: &[&str]) -> &'a str {
+    match split_last_word(s) {
+        Some((rest, word)) if skip.iter().any(|skip| word.eq_ignore_ascii_case(skip)) => rest,
+        _ => s,
+    }
+}
+
+/// Converts bits back to ID of the scheme.
+/// Bits must fit into the ID type.
+fn id_from_bits<S: Scheme>(bits: u128) -> S::Id {
+    match S::Id::try_from(bits) {
+        Ok(id) => id,
+        Err(_) => panic!("Bits do not fit into {}-bit scheme ID", S::BITS),
+    }
+}
+
+/// Returns the smallest and the largest IDs of the scheme
+/// followed by pseudo-random IDs.
+#[cfg(test)]
+fn sample_ids<S: Scheme>(count: usize) -> impl Iterator<Item = S::Id> {
+    let max = u128::MAX >> (128 - S::BITS);
+    let mut bits = 0x9e37_79b9_7f4a_7c15_f39c_c060_5ced_c834_u128;
+    let random = (0..count).map(move |_| {
+        bits = bits.rotate_left(23).wrapping_mul(0x2545_f491_4f6c_dd1d);
+        bits >> (128 - S::BITS)
+    });
+
+    core::iter::once(0)
+        .chain(core::iter::once(max))
+        .chain(random)
+        .map(id_from_bits::<S>)
+}
+
+/// Helpers to test serde functions of scheme modules with JSON.
+#[cfg(all(test, feature = "serde"))]
+mod json {
+    extern crate std;
+
+    use std::{string::String, vec::Vec};
+
+    /// Serializes value to JSON string with the serialize function.
+    pub fn to_json(
+        serialize: impl FnOnce(&mut serde_json::Serializer<&mut Vec<u8>>) -> serde_json::Result<()>,
+    ) -> String {
+        let mut out = Vec::new();
+        serialize(&mut serde_json::Serializer::new(&mut out)).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Deserializes value from JSON string with the deserialize function.
+    pub fn from_json<'de, T>(
+        json: &'de str,
+        deserialize: impl FnOnce(
+            &mut serde_json::Deserializer<serde_json::de::StrRead<'de>>,
+        ) -> serde_json::Result<T>,
+    ) -> serde_json::Result<T> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let value = deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(value)
+    }
+}
+
+/// Moves consecutive bits of the value, starting from the least significant,
+/// into bit ranges of words listed as `(offset, bits)` pairs.
+#[cfg(any(feature = "snowflake", feature = "ulid"))]
+fn spread_bits(value: u128, words: &[(u32, u32)]) -> u128 {
+    let mut bits = 0;
+    let mut shift = 0;
+    for &(offset, len) in words {
+        bits |= ((value >> shift) & ((1 << len) - 1)) << offset;
+        shift += len;
+    }
+    bits
+}
+
+/// Reverses [`spread_bits`].
+#[cfg(any(feature = "snowflake", feature = "ulid"))]
+fn gather_bits(bits: u128, words: &[(u32, u32)]) -> u128 {
+    let mut value = 0;
+    let mut shift = 0;
+    for &(offset, len) in words {
+        value |= ((bits >> offset) & ((1 << len) - 1)) << shift;
+        shift += len;
+    }
+    value
+}
+
+/// Deserializes a string and decodes it with `decode`.
+/// Borrowed and transient strings are decoded in place, without allocation.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_phrase<'de, T, D>(
+    deserializer: D,
+    decode: impl FnOnce(&str) -> Result<T, Error<'_>>,
+) -> Result<T, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    struct PhraseVisitor<F>(F);
+
+    impl<'de, T, F> serde::de::Visitor<'de> for PhraseVisitor<F>
+    where
+        F: FnOnce(&str) -> Result<T, Error<'_>>,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a phrase")
+        }
+
+        fn visit_str<R>(self, s: &str) -> Result<T, R>
+        where
+            R: serde::de::Error,
+        {
+            (self.0)(s).map_err(R::custom)
+        }
+    }
+
+    deserializer.deserialize_str(PhraseVisitor(decode))
+}
+
+/// Common interface of all phrase schemes.
+///
+/// Allows code to be generic over the scheme used to encode IDs.
+pub trait Scheme: Copy + fmt::Display {
+    /// Integer type of IDs encoded by this scheme.
+    type Id: Copy + Into<u128> + TryFrom<u128>;
+
+    /// Number of bits encoded by this scheme.
+    const BITS: u32;
+
+    /// Number of words in the phrase, without auxiliary words.
+    const WORDS: usize;
+
+    /// Encodes ID into a phrase using words from the dictionary.
+    ///
+    /// Bits of ID above [`Scheme::BITS`] are ignored.
+    /// Use [`Scheme::try_encode_with`] to reject such IDs.
+    fn encode_with<D: Dictionary>(id: Self::Id) -> Self;
+
+    /// Encodes ID into a phrase using words from the dictionary.
+    /// Returns `None` if ID has bits set above [`Scheme::BITS`].
+    fn try_encode_with<D: Dictionary>(id: Self::Id) -> Option<Self> {
+        if Self::BITS < 128 && id.into() >> Self::BITS != 0 {
+            return None;
+        }
+        Some(Self::encode_with::<D>(id))
+    }
+
+    /// Decodes ID from a phrase using words from the dictionary
+    /// matched by the matcher.
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<Self::Id, Error<'a>>;
+
+    /// Decodes ID from a phrase using words from the dictionary.
+    #[inline]
+    fn decode_with<D: Dictionary>(s: &str) -> Result<Self::Id, Error<'_>> {
+        Self::decode_by::<D>(s, &mut Exact)
+    }
+
+    /// Encodes ID into a phrase.
+    ///
+    /// Bits of ID above [`Scheme::BITS`] are ignored.
+    /// Use [`Scheme::try_encode`] to reject such IDs.
+    #[inline]
+    fn encode(id: Self::Id) -> Self {
+        Self::encode_with::<Standard>(id)
+    }
+
+    /// Encodes ID into a phrase.
+    /// Returns `None` if ID has bits set above [`Scheme::BITS`].
+    #[inline]
+    fn try_encode(id: Self::Id) -> Option<Self> {
+        Self::try_encode_with::<Standard>(id)
+    }
+
+    /// Decodes ID from a phrase.
+    #[inline]
+    fn decode(s: &str) -> Result<Self::Id, Error<'_>> {
+        Self::decode_with::<Standard>(s)
+    }
+
+    /// Decodes ID from a phrase replacing unrecognized words
+    /// with the nearest dictionary words.
+    #[inline]
+    fn decode_fuzzy(s: &str) -> Result<Corrected<Self>, Error<'_>> {
+        Self::decode_fuzzy_with::<Standard>(s)
+    }
+
+    /// Decodes ID from a phrase using words from the dictionary
+    /// replacing unrecognized words with the nearest dictionary words.
+    fn decode_fuzzy_with<D: Dictionary>(s: &str) -> Result<Corrected<Self>, Error<'_>> {
+        let mut fuzzy = Fuzzy::new();
+        let id = Self::decode_by::<D>(s, &mut fuzzy)?;
+        Ok(Corrected {
+            id,
+            phrase: Self::encode_with::<D>(id),
+            confidence: fuzzy.confidence(),
+        })
+    }
+
+    /// Decodes ID from a phrase where words may be abbreviated
+    /// to unique prefixes.
+    #[inline]
+    fn decode_abbreviated(s: &str) -> Result<Self::Id, Error<'_>> {
+        Self::decode_abbreviated_with::<Standard>(s)
+    }
+
+    /// Decodes ID from a phrase using words from the dictionary
+    /// where words may be abbreviated to unique prefixes.
+    #[inline]
+    fn decode_abbreviated_with<D: Dictionary>(s: &str) -> Result<Self::Id, Error<'_>> {
+        Self::decode_by::<D>(s, &mut Prefix)
+    }
+
+    /// Calls `f` for each word of the phrase and its class in order.
+    /// Auxiliary words are skipped.
+    fn for_each_word<E>(
+        &self,
+        f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E>;
+
+    /// Transform to hyphenated.
+    #[inline]
+    fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+
+    /// Transform to abbreviated.
+    #[inline]
+    fn abbreviated(self) -> Abbreviated<Self> {
+        Abbreviated::new(self)
+    }
+
+    /// Transform to abbreviated using words from the dictionary.
+    /// Must be the same dictionary the phrase was encoded with.
+    #[inline]
+    fn abbreviated_with<D: Dictionary>(self) -> Abbreviated<Self, D> {
+        Abbreviated::new(self)
+    }
+}
+
+/// Result of fuzzy decoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Corrected<S: Scheme> {
+    /// Decoded ID.
+    pub id: S::Id,
+
+    /// Phrase with corrected words.
+    pub phrase: S,
+
+    /// Confidence in corrections.
+    pub confidence: Confidence,
+}
+
 /// Wrapper that changes `Display` behavior of the scheme.
 /// Making it emit all words in one line with hyphen between them.
 /// Without auxiliary words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Hyphenated<T>(pub T);
+
+impl<S> fmt::Display for Hyphenated<S>
+where
+    S: Scheme,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        self.0.for_each_word(|_, word| {
+            if !first {
+                f.write_str("-")?;
+            }
+            first = false;
+            f.write_str(word)
+        })
+    }
+}
+
+/// Wrapper that changes `Display` behavior of the scheme.
+/// Making it emit shortest unique prefixes of words in one line with hyphen between them.
+/// Without auxiliary words.
+///
+/// Output can be decoded with [`Scheme::decode_abbreviated`].
+pub struct Abbreviated<S, D = Standard> {
+    phrase: S,
+    dictionary: PhantomData<fn() -> D>,
+}
+
+impl<S, D> Abbreviated<S, D> {
+    /// Wraps phrase encoded with words from the dictionary.
+    #[inline]
+    pub fn new(phrase: S) -> Self {
+        Abbreviated {
+            phrase,
+            dictionary: PhantomData,
+        }
+    }
+
+    /// Returns wrapped phrase.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.phrase
+    }
+}
+
+impl<S, D> Clone for Abbreviated<S, D>
+where
+    S: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Abbreviated::new(self.phrase.clone())
+    }
+}
+
+impl<S, D> Copy for Abbreviated<S, D> where S: Copy {}
+
+impl<S, D> fmt::Display for Abbreviated<S, D>
+where
+    S: Scheme,
+    D: Dictionary,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        self.phrase.for_each_word(|class, word| {
+            if !first {
+                f.write_str("-")?;
+            }
+            first = false;
+            f.write_str(D::word_mapper(class).unique_prefix(word))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{string::ToString, vec::Vec};
+
+    use super::*;
+
+    fn abbreviated_round_trip<S: Scheme>()
+    where
+        S::Id: fmt::Debug + PartialEq,
+    {
+        for id in sample_ids::<S>(200) {
+            let phrase = S::encode(id).abbreviated().to_string();
+            assert_eq!(S::decode_abbreviated(&phrase), Ok(id), "{}", phrase);
+
+            let phrase = phrase.to_ascii_uppercase();
+            assert_eq!(S::decode_abbreviated(&phrase), Ok(id), "{}", phrase);
+
+            let phrase = S::encode(id).to_string();
+            assert_eq!(S::decode_abbreviated(&phrase), Ok(id), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn abbreviated_round_trips() {
+        abbreviated_round_trip::<AdjectiveNoun>();
+        abbreviated_round_trip::<PrepositionPronoun>();
+        abbreviated_round_trip::<AdjectiveNounVerb>();
+        abbreviated_round_trip::<SimplePhrase>();
+        abbreviated_round_trip::<CompoundPhrase>();
+        abbreviated_round_trip::<Phrase>();
+        abbreviated_round_trip::<Punk>();
+        abbreviated_round_trip::<DoublePhrase>();
+        abbreviated_round_trip::<ComplexPhrase>();
+        abbreviated_round_trip::<VarPhrase>();
+        #[cfg(feature = "snowflake")]
+        abbreviated_round_trip::<SnowflakePhrase>();
+        #[cfg(feature = "ulid")]
+        abbreviated_round_trip::<UlidPhrase>();
+        #[cfg(feature = "uuid")]
+        abbreviated_round_trip::<UuidV4Phrase>();
+    }
+
+    #[test]
+    fn abbreviated_ambiguous_prefix() {
+        for id in sample_ids::<SimplePhrase>(200) {
+            let mut words = Vec::new();
+            let _ = SimplePhrase::encode(id).for_each_word(|class, word| {
+                words.push((class, word));
+                Ok::<_, ()>(())
+            });
+
+            // Prefix one letter shorter than unique one is shared with other nouns.
+            let (class, noun) = words[1];
+            let mapper = Standard::word_mapper(class);
+            let word = &noun[..mapper.unique_prefix(noun).len() - 1];
+            if word.is_empty() || mapper.find(word).is_some() {
+                continue;
+            }
+
+            let phrase = std::format!("{}-{}-{}-{}", words[0].1, word, words[2].1, words[3].1);
+            assert_eq!(
+                SimplePhrase::decode_abbreviated(&phrase),
+                Err(Error::Ambiguous { word, class }),
+                "{}",
+                phrase
+            );
+        }
+    }
+
+    #[test]
+    fn abbreviated_word_that_prefixes_another() {
+        let mut count = 0;
+        for &class in &[WordClass::Adjective, WordClass::Noun, WordClass::Adverb] {
+            let mapper = Standard::word_mapper(class);
+            for (idx, word) in mapper.words().iter().enumerate() {
+                let prefixes_another = mapper.words().iter().any(|other| {
+                    other.len() > word.len() && other[..word.len()].eq_ignore_ascii_case(word)
+                });
+                if prefixes_another {
+                    assert_eq!(mapper.unique_prefix(word), *word);
+                    assert_eq!(mapper.find_by_prefix(word), Ok(idx), "{}", word);
+                    count += 1;
+                }
+            }
+        }
+        // Dictionaries have such words, e.g. "pug" and "puggle".
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn abbreviated_empty_phrase() {
+        assert_eq!(
+            SimplePhrase::decode_abbreviated(""),
+            Err(Error::NotEnoughWords {
+                expected: SimplePhrase::WORDS,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn suggestions() {
+        let err = SimplePhrase::decode("The hearty kooly limbers learnedly").unwrap_err();
+        assert_eq!(
+            err,
+            Error::Unrecognized {
+                word: "kooly",
+                class: WordClass::Noun
+            }
+        );
+
+        let mut out = [""; 4];
+        let suggestions = err.suggestions(&mut out);
+        assert_eq!(suggestions.len(), 4);
+        assert_eq!(suggestions[0], "koolie");
+
+        let mapper = Standard::word_mapper(WordClass::Noun);
+        let mut nearest = [""; 8];
+        assert_eq!(err.suggestions(&mut nearest[..1]), ["koolie"]);
+        assert_eq!(err.suggestions(&mut nearest)[..4], *suggestions);
+        assert!(nearest.iter().all(|word| mapper.find(word).is_some()));
+
+        assert_eq!(err.suggestions(&mut []), [""; 0]);
+    }
+
+    #[test]
+    fn suggestions_longer_than_dictionary() {
+        let err = Error::Ambiguous {
+            word: "o",
+            class: WordClass::Preposition,
+        };
+        let words = Standard::word_mapper(WordClass::Preposition).words();
+
+        let mut out = [""; 1024];
+        let suggestions = err.suggestions(&mut out);
+        assert_eq!(suggestions.len(), words.len());
+
+        let mut suggestions = suggestions.to_vec();
+        suggestions.sort_unstable();
+        let mut words = words.to_vec();
+        words.sort_unstable();
+        assert_eq!(suggestions, words);
+    }
+
+    #[test]
+    fn suggestions_for_other_errors() {
+        let mut out = [""; 4];
+        assert_eq!(Error::TrailingWords.suggestions(&mut out), [""; 0]);
+        assert_eq!(Error::Overflow.suggestions(&mut out), [""; 0]);
+    }
+}
@@ -1,11 +1,12 @@
 use core::fmt;
 
 use crate::{
-    dict::{Adjective, Adverb, Mapper, Noun, Plural, Preposition, Verb},
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
     Hyphenated,
 };
 
-use super::{skip_one_of, string_to_words, Error};
+use super::{skip_one_of, string_to_words, Error, Scheme};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Phrase {
@@ -50,31 +51,73 @@ impl fmt::Display for Phrase {
     }
 }
 
-impl fmt::Display for Hyphenated<Phrase> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}-{}-{}-{}-{}-{}-{}-{}",
-            self.0.adjective1,
-            self.0.adjective2,
-            self.0.noun1,
-            self.0.verb,
-            self.0.adverb,
-            self.0.preposition,
-            self.0.adjective3,
-            self.0.noun2
-        )
+impl Scheme for Phrase {
+    type Id = u64;
+
+    const BITS: u32 = 64;
+    const WORDS: usize = 8;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: u64) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u64, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Adjective, self.adjective1),
+            (WordClass::Adjective, self.adjective2),
+            (WordClass::Noun, self.noun1),
+            (WordClass::VerbPlural, self.verb),
+            (WordClass::Adverb, self.adverb),
+            (WordClass::Preposition, self.preposition),
+            (WordClass::Adjective, self.adjective3),
+            (WordClass::Noun, self.noun2),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
     }
 }
 
+/// Offset and number of bits encoded by each word,
+/// in order of words in the phrase.
+#[cfg(feature = "snowflake")]
+pub(super) const WORD_BITS: [(u32, u32); 8] = [
+    (0, 8),
+    (8, 8),
+    (24, 8),
+    (40, 9),
+    (49, 9),
+    (58, 6),
+    (16, 8),
+    (32, 8),
+];
+
 /// Encodes bits into a phrase.
 /// For 64-bit ids.
 pub fn encode(bits: u64) -> Phrase {
-    let ([adjective1, adjective2, adjective3], bits) = Adjective::encode_words(bits.into());
-    let ([noun1, noun2], bits) = Noun::encode_words(bits);
-    let (verb, bits) = Verb::<Plural>::encode_word(bits);
-    let (adverb, bits) = Adverb::encode_word(bits);
-    let (preposition, bits) = Preposition::encode_word(bits);
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into a phrase.
+/// For 64-bit ids.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(bits: u64) -> Phrase {
+    let ([adjective1, adjective2, adjective3], bits) = D::Adjective::encode_words(bits.into());
+    let ([noun1, noun2], bits) = D::Noun::encode_words(bits);
+    let (verb, bits) = D::VerbPlural::encode_word(bits);
+    let (adverb, bits) = D::Adverb::encode_word(bits);
+    let (preposition, bits) = D::Preposition::encode_word(bits);
 
     debug_assert_eq!(bits, 0);
 
@@ -92,7 +135,24 @@ pub fn encode(bits: u64) -> Phrase {
 
 /// Decodes a phrase.
 /// For 64-bit ids.
-pub fn decode(s: &str) -> Result<u64, Error> {
+pub fn decode(s: &str) -> Result<u64, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes a phrase.
+/// For 64-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u64, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes a phrase.
+/// For 64-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u64, Error<'a>> {
     let mut iter = string_to_words(s);
 
     skip_one_of(&mut iter, &["a", "the"]);
@@ -140,14 +200,11 @@ pub fn decode(s: &str) -> Result<u64, Error> {
     let adjectives = [adjective3, adjective2, adjective1];
 
     let mut bits = 0;
-    bits = Preposition::decode_word(preposition, bits)
-        .ok_or(Error::Unrecognized { word: preposition })?;
-    bits = Adverb::decode_word(adverb, bits).ok_or(Error::Unrecognized { word: adverb })?;
-    bits = Verb::<Plural>::decode_word(verb, bits).ok_or(Error::Unrecognized { word: verb })?;
-    bits = Noun::decode_words(nouns, bits).map_err(|i| Error::Unrecognized { word: nouns[i] })?;
-    bits = Adjective::decode_words(adjectives, bits).map_err(|i| Error::Unrecognized {
-        word: adjectives[i],
-    })?;
+    bits = D::Preposition::decode_word(preposition, bits, matcher)?;
+    bits = D::Adverb::decode_word(adverb, bits, matcher)?;
+    bits = D::VerbPlural::decode_word(verb, bits, matcher)?;
+    bits = D::Noun::decode_words(nouns, bits, matcher)?;
+    bits = D::Adjective::decode_words(adjectives, bits, matcher)?;
 
     Ok(bits as u64)
 }
@@ -158,11 +215,8 @@ where
     T: Copy + Into<u64>,
     S: serde::ser::Serializer,
 {
-    use alloc::string::ToString;
-    use serde::Serialize;
-
     let an = encode((*value).into());
-    an.to_string().serialize(serializer)
+    serializer.collect_str(&an)
 }
 
 #[cfg(feature = "serde")]
@@ -171,11 +225,8 @@ where
     u64: Into<T>,
     D: serde::de::Deserializer<'de>,
 {
-    use alloc::borrow::Cow;
-
-    let s = <Cow<str> as serde::de::Deserialize>::deserialize(deserializer)?;
-    match decode(&*s) {
-        Err(err) => Err(serde::de::Error::custom(err)),
-        Ok(id) => Ok(id.into()),
-    }
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
 }
+
+#[cfg(feature = "serde")]
+super::with::modules!(Phrase: u64, |id| Ok(super::encode(id)));
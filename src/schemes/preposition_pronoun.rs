@@ -0,0 +1,157 @@
+use core::fmt;
+
+use crate::{
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
+    Hyphenated,
+};
+
+use super::{string_to_words, Error, Scheme};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PrepositionPronoun {
+    pub preposition: &'static str,
+    pub pronoun: &'static str,
+}
+
+impl PrepositionPronoun {
+    /// Encodes bits into `preposition pronoun` scheme
+    #[inline]
+    pub fn encode(bits: u8) -> Self {
+        encode(bits)
+    }
+
+    /// Transform to hyphenated.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+}
+
+impl fmt::Display for PrepositionPronoun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{} {}",
+            &self.preposition.chars().next().unwrap().to_uppercase(),
+            &self.preposition[1..],
+            self.pronoun
+        )
+    }
+}
+
+impl Scheme for PrepositionPronoun {
+    type Id = u8;
+
+    const BITS: u32 = 8;
+    const WORDS: usize = 2;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: u8) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u8, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Preposition, self.preposition),
+            (WordClass::ObjectPronoun, self.pronoun),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
+    }
+}
+
+/// Encodes bits into `preposition-pronoun` scheme
+/// For 8-bit ids.
+pub fn encode(bits: u8) -> PrepositionPronoun {
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into `preposition-pronoun` scheme
+/// For 8-bit ids.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(bits: u8) -> PrepositionPronoun {
+    let (preposition, bits) = D::Preposition::encode_word(bits.into());
+    let (pronoun, bits) = D::ObjectPronoun::encode_word(bits);
+
+    debug_assert_eq!(bits, 0);
+
+    PrepositionPronoun {
+        preposition,
+        pronoun,
+    }
+}
+
+/// Decodes `preposition-pronoun` scheme
+/// For 8-bit ids.
+pub fn decode(s: &str) -> Result<u8, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes `preposition-pronoun` scheme
+/// For 8-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u8, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes `preposition-pronoun` scheme
+/// For 8-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u8, Error<'a>> {
+    let mut iter = string_to_words(s);
+
+    let preposition = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 2,
+        actual: 0,
+    })?;
+    let pronoun = iter.next().ok_or(Error::NotEnoughWords {
+        expected: 2,
+        actual: 1,
+    })?;
+
+    if iter.next().is_some() {
+        return Err(Error::TrailingWords);
+    }
+
+    let mut bits = 0;
+    bits = D::ObjectPronoun::decode_word(pronoun, bits, matcher)?;
+    bits = D::Preposition::decode_word(preposition, bits, matcher)?;
+    Ok(bits as u8)
+}
+
+#[cfg(feature = "serde")]
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<u8>,
+    S: serde::ser::Serializer,
+{
+    let an = encode((*value).into());
+    serializer.collect_str(&an)
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    u8: Into<T>,
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(PrepositionPronoun: u8, |id| Ok(super::encode(id)));
@@ -1,11 +1,12 @@
 use core::fmt;
 
 use crate::{
-    dict::{Adjective, Mapper, ObjectPronoun, Singular, Verb},
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
     Hyphenated,
 };
 
-use super::{string_to_words, Error};
+use super::{string_to_words, Error, Scheme};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Punk {
@@ -61,33 +62,52 @@ impl fmt::Display for Punk {
     }
 }
 
-impl fmt::Display for Hyphenated<Punk> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}-{}-{}-{}-{}-{}-{}-{}-{}-{}-{}-{}",
-            &self.0.verbs[0],
-            &self.0.pronouns[0],
-            &self.0.verbs[1],
-            &self.0.pronouns[1],
-            &self.0.verbs[2],
-            &self.0.pronouns[2],
-            &self.0.verbs[3],
-            &self.0.pronouns[3],
-            &self.0.adjectives[0],
-            &self.0.adjectives[1],
-            &self.0.adjectives[2],
-            &self.0.adjectives[3],
-        )
+impl Scheme for Punk {
+    type Id = u64;
+
+    const BITS: u32 = 64;
+    const WORDS: usize = 12;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: u64) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u64, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for (verb, pronoun) in self.verbs.iter().zip(&self.pronouns) {
+            f(WordClass::VerbSingular, verb)?;
+            f(WordClass::ObjectPronoun, pronoun)?;
+        }
+        self.adjectives
+            .iter()
+            .try_for_each(|adjective| f(WordClass::Adjective, adjective))
     }
 }
 
 /// Encodes bits into a punky phrase.
 /// For 64-bit ids.
 pub fn encode(bits: u64) -> Punk {
-    let (verbs, bits) = Verb::<Singular>::encode_words(bits.into());
-    let (pronouns, bits) = ObjectPronoun::encode_words(bits.into());
-    let (adjectives, bits) = Adjective::encode_words(bits.into());
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into a punky phrase.
+/// For 64-bit ids.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(bits: u64) -> Punk {
+    let (verbs, bits) = D::VerbSingular::encode_words(bits.into());
+    let (pronouns, bits) = D::ObjectPronoun::encode_words(bits);
+    let (adjectives, bits) = D::Adjective::encode_words(bits);
 
     debug_assert_eq!(bits, 0);
 
@@ -100,7 +120,24 @@ pub fn encode(bits: u64) -> Punk {
 
 /// Decodes a punky phrase.
 /// For 64-bit ids.
-pub fn decode(s: &str) -> Result<u64, Error> {
+pub fn decode(s: &str) -> Result<u64, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes a punky phrase.
+/// For 64-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u64, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes a punky phrase.
+/// For 64-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u64, Error<'a>> {
     let mut split = string_to_words(s);
 
     let verb1 = split.next().ok_or(Error::NotEnoughWords {
@@ -161,13 +198,9 @@ pub fn decode(s: &str) -> Result<u64, Error> {
     let verbs = [verb4, verb3, verb2, verb1];
 
     let mut bits = 0;
-    bits = Adjective::decode_words(adjectives, bits).map_err(|i| Error::Unrecognized {
-        word: adjectives[i],
-    })?;
-    bits = ObjectPronoun::decode_words(pronouns, bits)
-        .map_err(|i| Error::Unrecognized { word: pronouns[i] })?;
-    bits = Verb::<Singular>::decode_words(verbs, bits)
-        .map_err(|i| Error::Unrecognized { word: verbs[i] })?;
+    bits = D::Adjective::decode_words(adjectives, bits, matcher)?;
+    bits = D::ObjectPronoun::decode_words(pronouns, bits, matcher)?;
+    bits = D::VerbSingular::decode_words(verbs, bits, matcher)?;
 
     Ok(bits as u64)
 }
@@ -178,11 +211,8 @@ where
     T: Copy + Into<u64>,
     S: serde::ser::Serializer,
 {
-    use alloc::string::ToString;
-    use serde::Serialize;
-
     let an = encode((*value).into());
-    an.to_string().serialize(serializer)
+    serializer.collect_str(&an)
 }
 
 #[cfg(feature = "serde")]
@@ -191,11 +221,8 @@ where
     u64: Into<T>,
     D: serde::de::Deserializer<'de>,
 {
-    use alloc::borrow::Cow;
-
-    let s = <Cow<str> as serde::de::Deserialize>::deserialize(deserializer)?;
-    match decode(&*s) {
-        Err(err) => Err(serde::de::Error::custom(err)),
-        Ok(id) => Ok(id.into()),
-    }
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
 }
+
+#[cfg(feature = "serde")]
+super::with::modules!(Punk: u64, |id| Ok(super::encode(id)));
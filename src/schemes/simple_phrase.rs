@@ -1,11 +1,12 @@
 use core::fmt;
 
 use crate::{
-    dict::{Adjective, Adverb, Mapper, Noun, Plural, Verb},
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
     Hyphenated,
 };
 
-use super::{skip_one_of, string_to_words, Error};
+use super::{skip_one_of, string_to_words, Error, Scheme};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SimplePhrase {
@@ -39,23 +40,54 @@ impl fmt::Display for SimplePhrase {
     }
 }
 
-impl fmt::Display for Hyphenated<SimplePhrase> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}-{}-{}-{}",
-            self.0.adjective, self.0.noun, self.0.verb, self.0.adverb
-        )
+impl Scheme for SimplePhrase {
+    type Id = u32;
+
+    const BITS: u32 = 32;
+    const WORDS: usize = 4;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: u32) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u32, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Adjective, self.adjective),
+            (WordClass::Noun, self.noun),
+            (WordClass::VerbPlural, self.verb),
+            (WordClass::Adverb, self.adverb),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
     }
 }
 
 /// Encodes bits into a simple phrase.
 /// For 32-bit ids.
 pub fn encode(bits: u32) -> SimplePhrase {
-    let (adjective, bits) = Adjective::encode_word(bits.into());
-    let (noun, bits) = Noun::encode_word(bits);
-    let (verb, bits) = Verb::<Plural>::encode_word(bits);
-    let (adverb, bits) = Adverb::encode_word(bits);
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into a simple phrase.
+/// For 32-bit ids.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(bits: u32) -> SimplePhrase {
+    let (adjective, bits) = D::Adjective::encode_word(bits.into());
+    let (noun, bits) = D::Noun::encode_word(bits);
+    let (verb, bits) = D::VerbPlural::encode_word(bits);
+    let (adverb, bits) = D::Adverb::encode_word(bits);
 
     debug_assert_eq!(bits, 0);
 
@@ -69,7 +101,24 @@ pub fn encode(bits: u32) -> SimplePhrase {
 
 /// Decodes a simple phrase.
 /// For 32-bit ids.
-pub fn decode(s: &str) -> Result<u32, Error> {
+pub fn decode(s: &str) -> Result<u32, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes a simple phrase.
+/// For 32-bit ids.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u32, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes a simple phrase.
+/// For 32-bit ids.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u32, Error<'a>> {
     let mut iter = string_to_words(s);
 
     skip_one_of(&mut iter, &["a", "the"]);
@@ -96,11 +145,10 @@ pub fn decode(s: &str) -> Result<u32, Error> {
     }
 
     let mut bits = 0;
-    bits = Adverb::decode_word(adverb, bits).ok_or(Error::Unrecognized { word: adverb })?;
-    bits = Verb::<Plural>::decode_word(verb, bits).ok_or(Error::Unrecognized { word: verb })?;
-    bits = Noun::decode_word(noun, bits).ok_or(Error::Unrecognized { word: noun })?;
-    bits =
-        Adjective::decode_word(adjective, bits).ok_or(Error::Unrecognized { word: adjective })?;
+    bits = D::Adverb::decode_word(adverb, bits, matcher)?;
+    bits = D::VerbPlural::decode_word(verb, bits, matcher)?;
+    bits = D::Noun::decode_word(noun, bits, matcher)?;
+    bits = D::Adjective::decode_word(adjective, bits, matcher)?;
 
     Ok(bits as u32)
 }
@@ -111,11 +159,8 @@ where
     T: Copy + Into<u32>,
     S: serde::ser::Serializer,
 {
-    use alloc::string::ToString;
-    use serde::Serialize;
-
     let an = encode((*value).into());
-    an.to_string().serialize(serializer)
+    serializer.collect_str(&an)
 }
 
 #[cfg(feature = "serde")]
@@ -124,11 +169,8 @@ where
     u32: Into<T>,
     D: serde::de::Deserializer<'de>,
 {
-    use alloc::borrow::Cow;
-
-    let s = <Cow<str> as serde::de::Deserialize>::deserialize(deserializer)?;
-    match decode(&*s) {
-        Err(err) => Err(serde::de::Error::custom(err)),
-        Ok(id) => Ok(id.into()),
-    }
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
 }
+
+#[cfg(feature = "serde")]
+super::with::modules!(SimplePhrase: u32, |id| Ok(super::encode(id)));
@@ -0,0 +1,201 @@
+//!
+//! Encoding of Twitter-style Snowflake IDs into phrases.
+//!
+//! Snowflake IDs consist of a sign bit, 41-bit millisecond timestamp,
+//! 10-bit machine ID and 12-bit sequence number, from the most significant bits.
+//! The first word of the phrase encodes the lower 4 bits of the sequence number
+//! and the lower 4 bits of the timestamp, next words encode the following
+//! 16 timestamp bits, which repeat only every 17 minutes, the rest of sequence number,
+//! machine ID, the rest of timestamp bits and the sign bit.
+//! So consecutive IDs differ from the very first word,
+//! while shared higher timestamp bits end up in the last words.
+//!
+//! Can be used as serde `with`-module for `u64` fields.
+//!
+
+use core::fmt;
+
+use crate::{
+    dict::{Dictionary, Standard, WordClass},
+    matcher::{Exact, Matcher},
+    Hyphenated,
+};
+
+use super::{gather_bits, phrase, spread_bits, Error, Phrase, Scheme};
+
+/// Phrase with bits of Snowflake ID placed by their entropy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SnowflakePhrase {
+    pub phrase: Phrase,
+}
+
+impl SnowflakePhrase {
+    /// Encodes Snowflake ID into a phrase.
+    #[inline]
+    pub fn encode(bits: u64) -> Self {
+        encode(bits)
+    }
+
+    /// Transform to hyphenated.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+}
+
+impl fmt::Display for SnowflakePhrase {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.phrase.fmt(f)
+    }
+}
+
+impl Scheme for SnowflakePhrase {
+    type Id = u64;
+
+    const BITS: u32 = 64;
+    const WORDS: usize = 8;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: u64) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u64, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    #[inline]
+    fn for_each_word<E>(
+        &self,
+        f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.phrase.for_each_word(f)
+    }
+}
+
+/// Fields of Snowflake ID as `(offset, bits)` pairs, in order of their entropy.
+const FIELDS: [(u32, u32); 7] = [
+    // Lower sequence number bits.
+    (0, 4),
+    // Lower timestamp bits.
+    (22, 4),
+    (26, 16),
+    // Higher sequence number bits.
+    (4, 8),
+    // Machine ID.
+    (12, 10),
+    // Higher timestamp bits.
+    (42, 21),
+    // Sign bit.
+    (63, 1),
+];
+
+/// Encodes Snowflake ID into a phrase.
+pub fn encode(bits: u64) -> SnowflakePhrase {
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes Snowflake ID into a phrase.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(bits: u64) -> SnowflakePhrase {
+    let value = gather_bits(bits.into(), &FIELDS);
+    let bits = spread_bits(value, &phrase::WORD_BITS);
+    SnowflakePhrase {
+        phrase: phrase::encode_with::<D>(bits as u64),
+    }
+}
+
+/// Decodes Snowflake ID from a phrase.
+pub fn decode(s: &str) -> Result<u64, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes Snowflake ID from a phrase.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u64, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes Snowflake ID from a phrase.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u64, Error<'a>> {
+    let bits = phrase::decode_by::<D>(s, matcher)?;
+    let value = gather_bits(bits.into(), &phrase::WORD_BITS);
+    Ok(spread_bits(value, &FIELDS) as u64)
+}
+
+#[cfg(feature = "serde")]
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<u64>,
+    S: serde::ser::Serializer,
+{
+    let an = encode((*value).into());
+    serializer.collect_str(&an)
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    u64: Into<T>,
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, |s| decode(s).map(Into::into))
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(SnowflakePhrase: u64, |id| Ok(super::encode(id)));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::schemes::sample_ids;
+
+    /// Timestamp bits offset.
+    const TIMESTAMP: u32 = 22;
+
+    fn first_word(id: u64) -> &'static str {
+        encode(id).for_each_word(|_, word| Err(word)).unwrap_err()
+    }
+
+    fn round_trip(id: u64) {
+        let phrase = encode(id);
+        assert_eq!(decode(&phrase.to_string()), Ok(id), "{}", phrase);
+        assert_eq!(
+            decode(&phrase.hyphenated().to_string()),
+            Ok(id),
+            "{}",
+            phrase
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(0);
+        round_trip(u64::MAX);
+        round_trip(1_288_834_974_657 << TIMESTAMP);
+        sample_ids::<SnowflakePhrase>(1000).for_each(round_trip);
+    }
+
+    #[test]
+    fn consecutive_ids_differ_in_first_word() {
+        for id in sample_ids::<SnowflakePhrase>(1000) {
+            let id = id & (u64::MAX >> 1);
+            assert_ne!(first_word(id), first_word(id + 1), "{}", id);
+            let next = id + (1 << TIMESTAMP);
+            assert_ne!(first_word(id), first_word(next), "{}", id);
+        }
+    }
+}
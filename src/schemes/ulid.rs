@@ -0,0 +1,179 @@
+//!
+//! Encoding of [`Ulid`]s into complex phrases.
+//!
+//! ULIDs start with 48-bit timestamp followed by 80 random bits.
+//! Random bits are encoded by the first words of the phrase,
+//! and timestamp bits follow from the least significant.
+//! So ULIDs created around the same time differ from the very first word,
+//! while shared higher timestamp bits end up in the last words.
+//!
+//! Can be used as serde `with`-module for `Ulid` fields.
+//!
+
+use core::fmt;
+
+use ::ulid::Ulid;
+
+use crate::{
+    dict::{Dictionary, Standard, WordClass},
+    matcher::{Exact, Matcher},
+    Hyphenated,
+};
+
+use super::{complex_phrase, gather_bits, spread_bits, ComplexPhrase, Error, Scheme};
+
+/// Complex phrase with bits of ULID placed by their entropy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UlidPhrase {
+    pub phrase: ComplexPhrase,
+}
+
+impl UlidPhrase {
+    /// Encodes ULID into a phrase.
+    #[inline]
+    pub fn encode(ulid: Ulid) -> Self {
+        encode(ulid)
+    }
+
+    /// Transform to hyphenated.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+}
+
+impl fmt::Display for UlidPhrase {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.phrase.fmt(f)
+    }
+}
+
+impl Scheme for UlidPhrase {
+    type Id = Ulid;
+
+    const BITS: u32 = 128;
+    const WORDS: usize = 16;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: Ulid) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<Ulid, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    #[inline]
+    fn for_each_word<E>(
+        &self,
+        f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.phrase.for_each_word(f)
+    }
+}
+
+/// Encodes ULID into a complex phrase.
+pub fn encode(ulid: Ulid) -> UlidPhrase {
+    encode_with::<Standard>(ulid)
+}
+
+/// Encodes ULID into a complex phrase.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(ulid: Ulid) -> UlidPhrase {
+    let bits = spread_bits(ulid.into(), &complex_phrase::WORD_BITS);
+    UlidPhrase {
+        phrase: complex_phrase::encode_with::<D>(bits),
+    }
+}
+
+/// Decodes ULID from a complex phrase.
+pub fn decode(s: &str) -> Result<Ulid, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes ULID from a complex phrase.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<Ulid, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes ULID from a complex phrase.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<Ulid, Error<'a>> {
+    let bits = complex_phrase::decode_by::<D>(s, matcher)?;
+    Ok(gather_bits(bits, &complex_phrase::WORD_BITS).into())
+}
+
+#[cfg(feature = "serde")]
+pub fn serialize<S>(value: &Ulid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    let an = encode(*value);
+    serializer.collect_str(&an)
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Ulid, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, decode)
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(::ulid::Ulid, |id| Ok(super::encode(id)));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::schemes::sample_ids;
+
+    fn first_word(ulid: Ulid) -> &'static str {
+        encode(ulid).for_each_word(|_, word| Err(word)).unwrap_err()
+    }
+
+    fn round_trip(ulid: Ulid) {
+        let phrase = encode(ulid);
+        assert_eq!(decode(&phrase.to_string()), Ok(ulid), "{}", phrase);
+        assert_eq!(
+            decode(&phrase.hyphenated().to_string()),
+            Ok(ulid),
+            "{}",
+            phrase
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(Ulid::nil());
+        round_trip(Ulid::from(u128::MAX));
+        round_trip(Ulid::from_parts(1_469_918_176_385, 0));
+        sample_ids::<UlidPhrase>(1000).for_each(round_trip);
+    }
+
+    #[test]
+    fn consecutive_ids_differ_in_first_word() {
+        for ulid in sample_ids::<UlidPhrase>(1000) {
+            let bits = u128::from(ulid) >> 1;
+            assert_ne!(
+                first_word(Ulid::from(bits)),
+                first_word(Ulid::from(bits + 1)),
+                "{}",
+                bits
+            );
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//!
+//! Encoding of [`Uuid`]s into complex phrases.
+//!
+//! Can be used as serde `with`-module for `Uuid` fields.
+//! See [`uuid_v4`](crate::uuid_v4) for shorter phrases of random UUIDs.
+//!
+
+use ::uuid::Uuid;
+
+use crate::{
+    dict::{Dictionary, Standard},
+    matcher::{Exact, Matcher},
+};
+
+use super::{complex_phrase, ComplexPhrase, Error};
+
+/// Encodes UUID into a complex phrase.
+pub fn encode(uuid: Uuid) -> ComplexPhrase {
+    encode_with::<Standard>(uuid)
+}
+
+/// Encodes UUID into a complex phrase.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(uuid: Uuid) -> ComplexPhrase {
+    complex_phrase::encode_with::<D>(uuid.as_u128())
+}
+
+/// Decodes UUID from a complex phrase.
+pub fn decode(s: &str) -> Result<Uuid, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes UUID from a complex phrase.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<Uuid, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes UUID from a complex phrase.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<Uuid, Error<'a>> {
+    complex_phrase::decode_by::<D>(s, matcher).map(Uuid::from_u128)
+}
+
+#[cfg(feature = "serde")]
+pub fn serialize<S>(value: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    let an = encode(*value);
+    serializer.collect_str(&an)
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, decode)
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(::uuid::Uuid, |id| Ok(super::encode(id)));
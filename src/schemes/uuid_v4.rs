@@ -0,0 +1,375 @@
+//!
+//! Encoding of random UUIDs into phrases shorter than complex ones.
+//!
+//! Version 4 UUIDs have 6 fixed bits of version and variant.
+//! Only the 122 random bits are encoded.
+//!
+//! Can be used as serde `with`-module for `Uuid` fields.
+//! Serialization of other UUID versions fails.
+//!
+
+use core::fmt;
+
+use ::uuid::{Uuid, Variant};
+
+use crate::{
+    dict::{Dictionary, Mapper, Standard, WordClass},
+    matcher::{Exact, Matcher},
+    Hyphenated,
+};
+
+use super::{skip_one_of, string_to_words, Error, Scheme};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UuidV4Phrase {
+    pub adjective1: &'static str,
+    pub noun1: &'static str,
+    pub verb1: &'static str,
+    pub adverb1: &'static str,
+    pub preposition: &'static str,
+    pub adjective2: &'static str,
+    pub noun2: &'static str,
+    pub noun3: &'static str,
+    pub verb2: &'static str,
+    pub adverb2: &'static str,
+    pub adjective3: &'static str,
+    pub adjective4: &'static str,
+    pub noun4: &'static str,
+    pub verb3: &'static str,
+    pub verb4: &'static str,
+}
+
+impl UuidV4Phrase {
+    /// Encodes version 4 UUID into a phrase.
+    /// Returns `None` for other UUIDs.
+    #[inline]
+    pub fn try_encode_uuid(uuid: Uuid) -> Option<Self> {
+        encode(uuid)
+    }
+
+    /// Transform to hyphenated.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+}
+
+impl fmt::Display for UuidV4Phrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The {} {} {} {} {} the {} {} and the {} and {} {} that the {} {} {} {} and {}",
+            self.adjective1,
+            self.noun1,
+            self.verb1,
+            self.adverb1,
+            self.preposition,
+            self.adjective2,
+            self.noun2,
+            self.noun3,
+            self.verb2,
+            self.adverb2,
+            self.adjective3,
+            self.adjective4,
+            self.noun4,
+            self.verb3,
+            self.verb4
+        )
+    }
+}
+
+/// Encodes random bits of version 4 UUID.
+/// Use [`to_bits`] and [`from_bits`] to convert UUIDs.
+///
+/// [`Scheme::try_encode`] rejects IDs with bits set above 122,
+/// such as whole UUIDs that were not converted with [`to_bits`].
+impl Scheme for UuidV4Phrase {
+    type Id = u128;
+
+    const BITS: u32 = 122;
+    const WORDS: usize = 15;
+
+    fn encode_with<D: Dictionary>(id: u128) -> Self {
+        let ([adjective1, adjective2], bits) = D::Adjective::encode_words(id);
+        let ([adjective3, adjective4], bits) = D::Adjective::encode_words(bits);
+        let ([noun1, noun2], bits) = D::Noun::encode_words(bits);
+        let ([noun3, noun4], bits) = D::Noun::encode_words(bits);
+        let ([verb1, verb2], bits) = D::VerbPlural::encode_words(bits);
+        let ([verb3, verb4], bits) = D::VerbPlural::encode_words(bits);
+        let ([adverb1, adverb2], bits) = D::Adverb::encode_words(bits);
+        let (preposition, _) = D::Preposition::encode_word(bits);
+
+        UuidV4Phrase {
+            adjective1,
+            noun1,
+            verb1,
+            adverb1,
+            preposition,
+            adjective2,
+            noun2,
+            noun3,
+            verb2,
+            adverb2,
+            adjective3,
+            adjective4,
+            noun4,
+            verb3,
+            verb4,
+        }
+    }
+
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u128, Error<'a>> {
+        let mut iter = string_to_words(s);
+
+        skip_one_of(&mut iter, &["a", "the"]);
+
+        let adjective1 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 0,
+        })?;
+        let noun1 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 1,
+        })?;
+        let verb1 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 2,
+        })?;
+        let adverb1 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 3,
+        })?;
+        let preposition = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 4,
+        })?;
+
+        skip_one_of(&mut iter, &["a", "the"]);
+        let adjective2 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 5,
+        })?;
+        let noun2 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 6,
+        })?;
+
+        skip_one_of(&mut iter, &["and"]);
+        skip_one_of(&mut iter, &["a", "the"]);
+        let noun3 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 7,
+        })?;
+
+        skip_one_of(&mut iter, &["and"]);
+        let verb2 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 8,
+        })?;
+        let adverb2 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 9,
+        })?;
+
+        skip_one_of(&mut iter, &["that"]);
+        skip_one_of(&mut iter, &["a", "the"]);
+        let adjective3 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 10,
+        })?;
+        let adjective4 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 11,
+        })?;
+        let noun4 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 12,
+        })?;
+        let verb3 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 13,
+        })?;
+
+        skip_one_of(&mut iter, &["and"]);
+        let verb4 = iter.next().ok_or(Error::NotEnoughWords {
+            expected: 15,
+            actual: 14,
+        })?;
+
+        if iter.next().is_some() {
+            return Err(Error::TrailingWords);
+        }
+
+        let mut bits = 0;
+
+        bits = D::Preposition::decode_word(preposition, bits, matcher)?;
+        bits = D::Adverb::decode_words([adverb2, adverb1], bits, matcher)?;
+        bits = D::VerbPlural::decode_words([verb4, verb3], bits, matcher)?;
+        bits = D::VerbPlural::decode_words([verb2, verb1], bits, matcher)?;
+        bits = D::Noun::decode_words([noun4, noun3], bits, matcher)?;
+        bits = D::Noun::decode_words([noun2, noun1], bits, matcher)?;
+        bits = D::Adjective::decode_words([adjective4, adjective3], bits, matcher)?;
+        bits = D::Adjective::decode_words([adjective2, adjective1], bits, matcher)?;
+
+        Ok(bits)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        [
+            (WordClass::Adjective, self.adjective1),
+            (WordClass::Noun, self.noun1),
+            (WordClass::VerbPlural, self.verb1),
+            (WordClass::Adverb, self.adverb1),
+            (WordClass::Preposition, self.preposition),
+            (WordClass::Adjective, self.adjective2),
+            (WordClass::Noun, self.noun2),
+            (WordClass::Noun, self.noun3),
+            (WordClass::VerbPlural, self.verb2),
+            (WordClass::Adverb, self.adverb2),
+            (WordClass::Adjective, self.adjective3),
+            (WordClass::Adjective, self.adjective4),
+            (WordClass::Noun, self.noun4),
+            (WordClass::VerbPlural, self.verb3),
+            (WordClass::VerbPlural, self.verb4),
+        ]
+        .iter()
+        .try_for_each(|&(class, word)| f(class, word))
+    }
+}
+
+/// Mask of 62 random bits after variant.
+const LOW_MASK: u128 = (1 << 62) - 1;
+
+/// Mask of 12 random bits between version and variant.
+const MID_MASK: u128 = (1 << 12) - 1;
+
+/// Returns 122 random bits of version 4 UUID.
+/// Returns `None` for other UUIDs.
+pub fn to_bits(uuid: Uuid) -> Option<u128> {
+    if uuid.get_version_num() != 4 || uuid.get_variant() != Variant::RFC4122 {
+        return None;
+    }
+
+    let value = uuid.as_u128();
+    Some((value & LOW_MASK) | ((value >> 64) & MID_MASK) << 62 | (value >> 80) << 74)
+}
+
+/// Returns version 4 UUID with the specified random bits.
+/// Bits above 122 are ignored.
+pub fn from_bits(bits: u128) -> Uuid {
+    let value = (bits & LOW_MASK)
+        | 0b10 << 62
+        | ((bits >> 62) & MID_MASK) << 64
+        | 4 << 76
+        | (bits >> 74) << 80;
+
+    Uuid::from_u128(value)
+}
+
+/// Encodes version 4 UUID into a phrase.
+/// Returns `None` for other UUIDs.
+pub fn encode(uuid: Uuid) -> Option<UuidV4Phrase> {
+    encode_with::<Standard>(uuid)
+}
+
+/// Encodes version 4 UUID into a phrase.
+/// Returns `None` for other UUIDs.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(uuid: Uuid) -> Option<UuidV4Phrase> {
+    to_bits(uuid).map(UuidV4Phrase::encode_with::<D>)
+}
+
+/// Decodes version 4 UUID from a phrase.
+pub fn decode(s: &str) -> Result<Uuid, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes version 4 UUID from a phrase.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<Uuid, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes version 4 UUID from a phrase.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<Uuid, Error<'a>> {
+    UuidV4Phrase::decode_by::<D>(s, matcher).map(from_bits)
+}
+
+#[cfg(feature = "serde")]
+pub fn serialize<S>(value: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    match encode(*value) {
+        None => Err(serde::ser::Error::custom("UUID is not version 4")),
+        Some(an) => serializer.collect_str(&an),
+    }
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, decode)
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(::uuid::Uuid, |uuid| super::encode(uuid)
+    .ok_or("UUID is not version 4"));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::schemes::sample_ids;
+
+    #[test]
+    fn round_trips() {
+        for bits in sample_ids::<UuidV4Phrase>(1000) {
+            let uuid = from_bits(bits);
+            assert_eq!(uuid.get_version_num(), 4);
+            assert_eq!(to_bits(uuid), Some(bits));
+
+            let phrase = encode(uuid).unwrap().to_string();
+            assert_eq!(decode(&phrase), Ok(uuid), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn rejects_other_uuids() {
+        assert_eq!(encode(Uuid::nil()), None);
+        assert_eq!(encode(Uuid::from_u128(u128::MAX)), None);
+
+        // Version 1.
+        let uuid = Uuid::from_u128(from_bits(42).as_u128() ^ 5 << 76);
+        assert_eq!(encode(uuid), None);
+    }
+
+    #[test]
+    fn rejects_wide_ids() {
+        let max = (1 << 122) - 1;
+        assert!(UuidV4Phrase::try_encode(max).is_some());
+        assert_eq!(UuidV4Phrase::try_encode(max + 1), None);
+
+        let uuid = from_bits(u128::MAX).as_u128();
+        assert_eq!(UuidV4Phrase::try_encode(uuid), None);
+        assert_eq!(
+            UuidV4Phrase::try_encode(42),
+            Some(UuidV4Phrase::encode(42))
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//!
+//! Phrases with as many words as needed to encode the number.
+//!
+//! Words follow sentences
+//! `The {adjective} {noun} {verb} {adverb} {preposition} the {adjective} {noun}.`
+//! starting with `adjective noun` and adding words while there are bits left.
+//! Decoding infers the length from the number of words.
+//!
+//! Phrases of up to four words are the same as [`AdjectiveNoun`](crate::AdjectiveNoun),
+//! [`AdjectiveNounVerb`](crate::AdjectiveNounVerb) and [`SimplePhrase`](crate::SimplePhrase).
+//!
+//! ```
+//! use meme_id::{Scheme, VarPhrase};
+//!
+//! assert_eq!(VarPhrase::encode(1234).words().len(), 2);
+//! assert_eq!(VarPhrase::encode(u64::MAX.into()).words().len(), 8);
+//!
+//! let phrase = VarPhrase::encode(1234).to_string();
+//! assert_eq!(VarPhrase::decode(&phrase), Ok(1234));
+//! ```
+//!
+
+use core::fmt;
+
+use crate::{
+    dict::{Dictionary, Standard, WordClass},
+    matcher::{Exact, Matcher},
+    Hyphenated,
+};
+
+use super::{skip_one_of, string_to_words, Error, Scheme, ARTICLES, SENTENCE};
+
+/// Minimal number of words in a phrase.
+const MIN_WORDS: usize = 2;
+
+/// Number of words required for 128 bits.
+const MAX_WORDS: usize = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarPhrase {
+    words: [&'static str; MAX_WORDS],
+    len: usize,
+}
+
+impl VarPhrase {
+    /// Encodes bits into as few words as possible
+    #[inline]
+    pub fn encode(bits: u128) -> Self {
+        encode(bits)
+    }
+
+    /// Transform to hyphenated.
+    #[inline]
+    pub fn hyphenated(self) -> Hyphenated<Self> {
+        Hyphenated(self)
+    }
+
+    /// Returns words of the phrase.
+    #[inline]
+    pub fn words(&self) -> &[&'static str] {
+        &self.words[..self.len]
+    }
+}
+
+impl fmt::Display for VarPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (pos, word) in self.words().iter().enumerate() {
+            match pos % SENTENCE.len() {
+                0 if pos == 0 => f.write_str("The ")?,
+                0 => f.write_str(". The ")?,
+                5 => f.write_str(" the ")?,
+                _ => f.write_str(" ")?,
+            }
+            f.write_str(word)?;
+        }
+        Ok(())
+    }
+}
+
+impl Scheme for VarPhrase {
+    type Id = u128;
+
+    const BITS: u32 = 128;
+    const WORDS: usize = MAX_WORDS;
+
+    #[inline]
+    fn encode_with<D: Dictionary>(id: u128) -> Self {
+        encode_with::<D>(id)
+    }
+
+    #[inline]
+    fn decode_by<'a, D: Dictionary>(
+        s: &'a str,
+        matcher: &mut impl Matcher,
+    ) -> Result<u128, Error<'a>> {
+        decode_by::<D>(s, matcher)
+    }
+
+    fn for_each_word<E>(
+        &self,
+        mut f: impl FnMut(WordClass, &'static str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.words()
+            .iter()
+            .zip(SENTENCE.iter().cycle())
+            .try_for_each(|(&word, &class)| f(class, word))
+    }
+}
+
+/// Encodes bits into a variable length phrase.
+/// For ids up to 128 bits.
+pub fn encode(bits: u128) -> VarPhrase {
+    encode_with::<Standard>(bits)
+}
+
+/// Encodes bits into a variable length phrase.
+/// For ids up to 128 bits.
+/// Uses words from the specified dictionary.
+pub fn encode_with<D: Dictionary>(bits: u128) -> VarPhrase {
+    encode_zero_extended_with::<D>(bits, 0)
+}
+
+/// Encodes bits into a variable length phrase
+/// with as many words as the largest `width`-bit id needs,
+/// so all ids of the same width produce phrases of the same length.
+/// For ids up to 128 bits.
+pub fn encode_zero_extended(bits: u128, width: u32) -> VarPhrase {
+    encode_zero_extended_with::<Standard>(bits, width)
+}
+
+/// Encodes bits into a variable length phrase
+/// with as many words as the largest `width`-bit id needs,
+/// so all ids of the same width produce phrases of the same length.
+/// For ids up to 128 bits.
+/// Uses words from the specified dictionary.
+pub fn encode_zero_extended_with<D: Dictionary>(mut bits: u128, width: u32) -> VarPhrase {
+    let mut words = [""; MAX_WORDS];
+    let mut len = 0;
+    let mut covered = 0;
+
+    while len < MIN_WORDS || bits != 0 || covered < width.min(128) {
+        let class = SENTENCE[len % SENTENCE.len()];
+        let mapper = D::word_mapper(class);
+        let (word, rest) = mapper.encode_word(bits);
+        words[len] = word;
+        bits = rest;
+        covered += mapper.bits();
+        len += 1;
+    }
+
+    VarPhrase { words, len }
+}
+
+/// Decodes a variable length phrase.
+/// For ids up to 128 bits.
+pub fn decode(s: &str) -> Result<u128, Error<'_>> {
+    decode_with::<Standard>(s)
+}
+
+/// Decodes a variable length phrase.
+/// For ids up to 128 bits.
+/// Uses words from the specified dictionary.
+pub fn decode_with<D: Dictionary>(s: &str) -> Result<u128, Error<'_>> {
+    decode_by::<D>(s, &mut Exact)
+}
+
+/// Decodes a variable length phrase.
+/// For ids up to 128 bits.
+/// Uses words from the specified dictionary matched by the matcher.
+pub fn decode_by<'a, D: Dictionary>(
+    s: &'a str,
+    matcher: &mut impl Matcher,
+) -> Result<u128, Error<'a>> {
+    let mut iter = string_to_words(s);
+
+    let mut words = [""; MAX_WORDS];
+    let mut len = 0;
+
+    loop {
+        if ARTICLES.contains(&(len % SENTENCE.len())) {
+            skip_one_of(&mut iter, &["a", "the"]);
+        }
+
+        match iter.next() {
+            None => break,
+            Some(_) if len == MAX_WORDS => return Err(Error::TrailingWords),
+            Some(word) => {
+                words[len] = word;
+                len += 1;
+            }
+        }
+    }
+
+    if len < MIN_WORDS {
+        return Err(Error::NotEnoughWords {
+            expected: MIN_WORDS,
+            actual: len,
+        });
+    }
+
+    let mut bits = 0;
+    for (pos, &word) in words[..len].iter().enumerate().rev() {
+        let class = SENTENCE[pos % SENTENCE.len()];
+        let mapper = D::word_mapper(class);
+        let idx = matcher
+            .find(&mapper, word)
+            .map_err(|mismatch| mismatch.into_error(word, class))?;
+
+        if bits >> (128 - mapper.bits()) != 0 {
+            return Err(Error::Overflow);
+        }
+        bits = mapper.decode_index(idx, bits);
+    }
+
+    Ok(bits)
+}
+
+#[cfg(feature = "serde")]
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<u128>,
+    S: serde::ser::Serializer,
+{
+    let an = encode((*value).into());
+    serializer.collect_str(&an)
+}
+
+#[cfg(feature = "serde")]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: core::convert::TryFrom<u128>,
+    D: serde::de::Deserializer<'de>,
+{
+    super::deserialize_phrase(deserializer, |s| {
+        T::try_from(decode(s)?).map_err(|_| Error::Overflow)
+    })
+}
+
+#[cfg(feature = "serde")]
+super::with::modules!(VarPhrase: try_from u128, |id| Ok(super::encode(id)));
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{format, string::ToString, vec::Vec};
+
+    use super::*;
+    use crate::dict::{Adjective, Mapper, Noun};
+
+    /// Returns number of words needed for `bits` bits.
+    fn words_for(bits: u32) -> usize {
+        let mut covered = 0;
+        let mut len = 0;
+        while len < MIN_WORDS || covered < bits {
+            covered += Standard::word_mapper(SENTENCE[len % SENTENCE.len()]).bits();
+            len += 1;
+        }
+        len
+    }
+
+    #[test]
+    fn round_trips_all_widths() {
+        for width in 0..=128 {
+            let max = u128::MAX >> (128 - width.max(1));
+            for &id in &[max >> 1, max, max / 3] {
+                let phrase = encode(id);
+                let bits = 128 - id.leading_zeros();
+                assert_eq!(phrase.words().len(), words_for(bits), "{}", phrase);
+
+                let phrase = phrase.to_string();
+                assert_eq!(decode(&phrase), Ok(id), "{}", phrase);
+            }
+        }
+    }
+
+    #[test]
+    fn two_words() {
+        assert_eq!(encode(0).words().len(), MIN_WORDS);
+        assert_eq!(encode((1 << 19) - 1).words().len(), MIN_WORDS);
+        assert_eq!(encode(1 << 19).words().len(), MIN_WORDS + 1);
+        assert_eq!(encode_zero_extended(0, 20).words().len(), MIN_WORDS + 1);
+
+        let phrase = encode(1234).to_string();
+        assert_eq!(phrase.split(' ').count(), 3);
+        assert_eq!(decode(&phrase), Ok(1234));
+    }
+
+    #[test]
+    fn most_words() {
+        let phrase = encode(u128::MAX);
+        assert_eq!(phrase.words().len(), MAX_WORDS);
+        assert_eq!(encode(1 << 127).words().len(), MAX_WORDS);
+        assert_eq!(encode_zero_extended(0, 200).words().len(), MAX_WORDS);
+
+        let phrase = phrase.to_string();
+        assert_eq!(decode(&phrase), Ok(u128::MAX));
+
+        // Last adjective has room for 4 bits only.
+        let (adjective, _) = Adjective::encode_word(1 << 4);
+        let words: Vec<&str> = phrase.rsplitn(2, ' ').collect();
+        let overflow = format!("{} {}", words[1], adjective);
+        assert_eq!(decode(&overflow), Err(Error::Overflow));
+
+        // Sixteenth word is never needed.
+        let (noun, _) = Noun::encode_word(0);
+        let trailing = format!("{} {}", phrase, noun);
+        assert_eq!(decode(&trailing), Err(Error::TrailingWords));
+    }
+
+    #[test]
+    fn not_enough_words() {
+        assert_eq!(
+            decode(""),
+            Err(Error::NotEnoughWords {
+                expected: MIN_WORDS,
+                actual: 0
+            })
+        );
+        assert_eq!(
+            decode("The"),
+            Err(Error::NotEnoughWords {
+                expected: MIN_WORDS,
+                actual: 0
+            })
+        );
+        let phrase = encode(1234);
+        assert_eq!(
+            decode(phrase.words()[0]),
+            Err(Error::NotEnoughWords {
+                expected: MIN_WORDS,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_sentences() {
+        let phrase = encode(u64::MAX.into());
+        let words = phrase.words();
+
+        // Words swapped across classes.
+        let swapped = format!("The {} {}", words[1], words[0]);
+        assert_eq!(
+            decode(&swapped),
+            Err(Error::Unrecognized {
+                word: words[0],
+                class: WordClass::Noun
+            })
+        );
+
+        // Article in place of a word shifts following words to other classes.
+        let article = format!("The {} the {}", words[0], words[1]);
+        assert_eq!(
+            decode(&article),
+            Err(Error::Unrecognized {
+                word: words[1],
+                class: WordClass::VerbPlural
+            })
+        );
+
+        // Articles are optional and punctuation is ignored.
+        let bare = words.join(" ");
+        assert_eq!(decode(&bare), Ok(u64::MAX.into()));
+        let hyphenated = phrase.hyphenated().to_string();
+        assert_eq!(decode(&hyphenated), Ok(u64::MAX.into()));
+    }
+}
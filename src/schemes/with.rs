@@ -0,0 +1,840 @@
+//!
+//! Shared implementation of `option`, `seq`, `keys` and `lenient` serde `with`-modules of schemes.
+//!
+//! Each value is encoded into a phrase with `encode` function
+//! and decoded back with `decode` function of the scheme module.
+//!
+
+use core::{
+    convert::TryFrom,
+    fmt,
+    iter::{self, FromIterator},
+    marker::PhantomData,
+};
+
+use serde::{
+    de::{DeserializeSeed, Deserializer, MapAccess, SeqAccess, Unexpected, Visitor},
+    ser::{Serialize, Serializer},
+    Deserialize,
+};
+
+use super::{deserialize_phrase, Error, Scheme};
+
+/// Generates `option`, `seq` and `keys` serde `with`-modules in the scheme module
+/// and, when scheme type is given, `lenient` module too.
+///
+/// IDs are converted into `$id` with `Into` before encoding with the expression
+/// that returns either the phrase or an error message.
+/// The expression is expanded in the generated modules, so it reaches scheme items with `super::`.
+/// Decoded IDs are converted back with `Into` or, with `try_from`, with `TryFrom`.
+macro_rules! modules {
+    (@collections $id:ty, |$v:ident| $encode:expr, [$($bound:tt)*], |$c:ident| $convert:expr) => {
+        /// Serde `with`-module for `Option` fields.
+        pub mod option {
+            use super::super::with;
+
+            pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: Copy + Into<$id>,
+                S: serde::ser::Serializer,
+            {
+                with::serialize_option(value, serializer, |value| {
+                    let $v: $id = (*value).into();
+                    $encode
+                })
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+            where
+                $($bound)*,
+                D: serde::de::Deserializer<'de>,
+            {
+                with::deserialize_option(deserializer, |s| {
+                    super::decode(s).and_then(|$c| $convert.ok_or($crate::Error::Overflow))
+                })
+            }
+        }
+
+        /// Serde `with`-module for sequence fields, such as `Vec` and `HashSet`.
+        pub mod seq {
+            use super::super::with;
+
+            pub fn serialize<'a, C, T, S>(values: &'a C, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                &'a C: IntoIterator<Item = &'a T>,
+                T: Copy + Into<$id> + 'a,
+                S: serde::ser::Serializer,
+            {
+                with::serialize_seq(values, serializer, |value| {
+                    let $v: $id = (*value).into();
+                    $encode
+                })
+            }
+
+            pub fn deserialize<'de, C, T, D>(deserializer: D) -> Result<C, D::Error>
+            where
+                C: core::iter::FromIterator<T>,
+                $($bound)*,
+                D: serde::de::Deserializer<'de>,
+            {
+                with::deserialize_seq(deserializer, |s| {
+                    super::decode(s).and_then(|$c| $convert.ok_or($crate::Error::Overflow))
+                })
+            }
+        }
+
+        /// Serde `with`-module for map fields, such as `HashMap` and `BTreeMap`, with IDs as keys.
+        pub mod keys {
+            use super::super::with;
+
+            pub fn serialize<'a, M, T, V, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                &'a M: IntoIterator<Item = (&'a T, &'a V)>,
+                T: Copy + Into<$id> + 'a,
+                V: serde::Serialize + 'a,
+                S: serde::ser::Serializer,
+            {
+                with::serialize_keys(map, serializer, |value| {
+                    let $v: $id = (*value).into();
+                    $encode
+                })
+            }
+
+            pub fn deserialize<'de, M, T, V, D>(deserializer: D) -> Result<M, D::Error>
+            where
+                M: core::iter::FromIterator<(T, V)>,
+                $($bound)*,
+                V: serde::Deserialize<'de>,
+                D: serde::de::Deserializer<'de>,
+            {
+                with::deserialize_keys(deserializer, |s| {
+                    super::decode(s).and_then(|$c| $convert.ok_or($crate::Error::Overflow))
+                })
+            }
+        }
+    };
+    (@lenient $scheme:ident, $id:ty, [$($bound:tt)*], |$c:ident| $convert:expr) => {
+        /// Serde `with`-module that serializes IDs as phrases in human-readable formats
+        /// and as integers in binary formats.
+        /// Deserializes from phrases, integers and strings with decimal or `0x`-prefixed hex numbers.
+        pub mod lenient {
+            use super::{super::with, $scheme};
+
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: Copy + Into<$id>,
+                S: serde::ser::Serializer,
+            {
+                with::serialize_lenient::<$scheme, S>((*value).into(), serializer)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                $($bound)*,
+                D: serde::de::Deserializer<'de>,
+            {
+                with::deserialize_lenient::<$scheme, T, D>(deserializer, |$c| $convert)
+            }
+        }
+    };
+    ($scheme:ident: try_from $id:ty, |$v:ident| $encode:expr) => {
+        $crate::schemes::with::modules!(
+            @collections $id,
+            |$v| $encode,
+            [T: core::convert::TryFrom<$id>],
+            |id| T::try_from(id).ok()
+        );
+        $crate::schemes::with::modules!(
+            @lenient $scheme,
+            $id,
+            [T: core::convert::TryFrom<$id>],
+            |id| T::try_from(id).ok()
+        );
+    };
+    ($scheme:ident: $id:ty, |$v:ident| $encode:expr) => {
+        $crate::schemes::with::modules!(
+            @collections $id,
+            |$v| $encode,
+            [$id: Into<T>],
+            |id| Some::<T>(id.into())
+        );
+        $crate::schemes::with::modules!(
+            @lenient $scheme,
+            $id,
+            [$id: Into<T>],
+            |id| Some::<T>(id.into())
+        );
+    };
+    ($id:ty, |$v:ident| $encode:expr) => {
+        $crate::schemes::with::modules!(
+            @collections $id,
+            |$v| $encode,
+            [$id: Into<T>],
+            |id| Some::<T>(id.into())
+        );
+    };
+}
+
+pub(super) use modules;
+
+/// Serializes referenced value as a phrase produced by `encode`.
+struct PhraseOf<'a, T, F> {
+    value: &'a T,
+    encode: &'a F,
+}
+
+impl<T, F, P> Serialize for PhraseOf<'_, T, F>
+where
+    F: Fn(&T) -> Result<P, &'static str>,
+    P: fmt::Display,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match (self.encode)(self.value) {
+            Err(msg) => Err(serde::ser::Error::custom(msg)),
+            Ok(phrase) => serializer.collect_str(&phrase),
+        }
+    }
+}
+
+/// Deserializes value from a phrase with `decode`.
+struct PhraseSeed<'a, F>(&'a F);
+
+impl<'de, T, F> DeserializeSeed<'de> for PhraseSeed<'_, F>
+where
+    F: Fn(&str) -> Result<T, Error<'_>>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_phrase(deserializer, self.0)
+    }
+}
+
+pub(super) fn serialize_option<T, P, S>(
+    value: &Option<T>,
+    serializer: S,
+    encode: impl Fn(&T) -> Result<P, &'static str>,
+) -> Result<S::Ok, S::Error>
+where
+    P: fmt::Display,
+    S: Serializer,
+{
+    match value {
+        None => serializer.serialize_none(),
+        Some(value) => serializer.serialize_some(&PhraseOf {
+            value,
+            encode: &encode,
+        }),
+    }
+}
+
+pub(super) fn deserialize_option<'de, T, D>(
+    deserializer: D,
+    decode: impl Fn(&str) -> Result<T, Error<'_>>,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct OptionVisitor<F>(F);
+
+    impl<'de, T, F> Visitor<'de> for OptionVisitor<F>
+    where
+        F: Fn(&str) -> Result<T, Error<'_>>,
+    {
+        type Value = Option<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an optional phrase")
+        }
+
+        fn visit_none<E>(self) -> Result<Option<T>, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E>(self) -> Result<Option<T>, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize_phrase(deserializer, self.0).map(Some)
+        }
+    }
+
+    deserializer.deserialize_option(OptionVisitor(decode))
+}
+
+pub(super) fn serialize_seq<'a, T, P, S>(
+    values: impl IntoIterator<Item = &'a T>,
+    serializer: S,
+    encode: impl Fn(&T) -> Result<P, &'static str>,
+) -> Result<S::Ok, S::Error>
+where
+    T: 'a,
+    P: fmt::Display,
+    S: Serializer,
+{
+    serializer.collect_seq(values.into_iter().map(|value| PhraseOf {
+        value,
+        encode: &encode,
+    }))
+}
+
+pub(super) fn deserialize_seq<'de, C, T, D>(
+    deserializer: D,
+    decode: impl Fn(&str) -> Result<T, Error<'_>>,
+) -> Result<C, D::Error>
+where
+    C: FromIterator<T>,
+    D: Deserializer<'de>,
+{
+    struct SeqVisitor<C, F>(F, PhantomData<fn() -> C>);
+
+    impl<'de, C, T, F> Visitor<'de> for SeqVisitor<C, F>
+    where
+        C: FromIterator<T>,
+        F: Fn(&str) -> Result<T, Error<'_>>,
+    {
+        type Value = C;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a sequence of phrases")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<C, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut error = None;
+            let values = iter::from_fn(|| {
+                seq.next_element_seed(PhraseSeed(&self.0))
+                    .unwrap_or_else(|err| {
+                        error = Some(err);
+                        None
+                    })
+            })
+            .collect();
+            match error {
+                None => Ok(values),
+                Some(err) => Err(err),
+            }
+        }
+    }
+
+    deserializer.deserialize_seq(SeqVisitor(decode, PhantomData))
+}
+
+pub(super) fn serialize_keys<'a, K, V, P, S>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    serializer: S,
+    encode: impl Fn(&K) -> Result<P, &'static str>,
+) -> Result<S::Ok, S::Error>
+where
+    K: 'a,
+    V: Serialize + 'a,
+    P: fmt::Display,
+    S: Serializer,
+{
+    serializer.collect_map(entries.into_iter().map(|(value, v)| {
+        (
+            PhraseOf {
+                value,
+                encode: &encode,
+            },
+            v,
+        )
+    }))
+}
+
+pub(super) fn deserialize_keys<'de, M, K, V, D>(
+    deserializer: D,
+    decode: impl Fn(&str) -> Result<K, Error<'_>>,
+) -> Result<M, D::Error>
+where
+    M: FromIterator<(K, V)>,
+    V: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    struct MapVisitor<M, V, F>(F, PhantomData<fn() -> (M, V)>);
+
+    impl<'de, M, K, V, F> Visitor<'de> for MapVisitor<M, V, F>
+    where
+        M: FromIterator<(K, V)>,
+        V: Deserialize<'de>,
+        F: Fn(&str) -> Result<K, Error<'_>>,
+    {
+        type Value = M;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a map with phrase keys")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<M, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut error = None;
+            let entries = iter::from_fn(|| {
+                map.next_entry_seed(PhraseSeed(&self.0), PhantomData)
+                    .unwrap_or_else(|err| {
+                        error = Some(err);
+                        None
+                    })
+            })
+            .collect();
+            match error {
+                None => Ok(entries),
+                Some(err) => Err(err),
+            }
+        }
+    }
+
+    deserializer.deserialize_map(MapVisitor(decode, PhantomData))
+}
+
+/// Serializes ID as a phrase of scheme `S` in human-readable formats
+/// and as an integer otherwise.
+pub(super) fn serialize_lenient<S, R>(id: S::Id, serializer: R) -> Result<R::Ok, R::Error>
+where
+    S: Scheme,
+    S::Id: Serialize,
+    R: Serializer,
+{
+    if serializer.is_human_readable() {
+        match S::try_encode(id) {
+            None => Err(serde::ser::Error::custom(format_args!(
+                "ID doesn't fit into {} bits",
+                S::BITS
+            ))),
+            Some(phrase) => serializer.collect_str(&phrase),
+        }
+    } else {
+        id.serialize(serializer)
+    }
+}
+
+/// Deserializes ID from a phrase of scheme `S`, an integer,
+/// or a string with decimal or `0x`-prefixed hexadecimal number
+/// in human-readable formats, and from an integer otherwise.
+///
+/// Decoded ID is converted into `T` with `convert`, that returns `None` if it doesn't fit.
+pub(super) fn deserialize_lenient<'de, S, T, D>(
+    deserializer: D,
+    convert: impl Fn(S::Id) -> Option<T>,
+) -> Result<T, D::Error>
+where
+    S: Scheme,
+    S::Id: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    struct LenientVisitor<S, F>(F, PhantomData<fn() -> S>);
+
+    impl<S, T, F> LenientVisitor<S, F>
+    where
+        S: Scheme,
+        F: Fn(S::Id) -> Option<T>,
+    {
+        fn number<E>(self, n: u128) -> Result<T, E>
+        where
+            E: serde::de::Error,
+        {
+            if S::BITS < 128 && n >> S::BITS != 0 {
+                return Err(self.out_of_range());
+            }
+            match S::Id::try_from(n).ok().and_then(&self.0) {
+                None => Err(self.out_of_range()),
+                Some(id) => Ok(id),
+            }
+        }
+
+        fn out_of_range<E>(&self) -> E
+        where
+            E: serde::de::Error,
+        {
+            E::invalid_value(Unexpected::Other("number out of range"), self)
+        }
+    }
+
+    impl<'de, S, T, F> Visitor<'de> for LenientVisitor<S, F>
+    where
+        S: Scheme,
+        F: Fn(S::Id) -> Option<T>,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a phrase or a number")
+        }
+
+        fn visit_u64<E>(self, n: u64) -> Result<T, E>
+        where
+            E: serde::de::Error,
+        {
+            self.number(n.into())
+        }
+
+        fn visit_u128<E>(self, n: u128) -> Result<T, E>
+        where
+            E: serde::de::Error,
+        {
+            self.number(n)
+        }
+
+        fn visit_i64<E>(self, n: i64) -> Result<T, E>
+        where
+            E: serde::de::Error,
+        {
+            match u64::try_from(n) {
+                Ok(n) => self.number(n.into()),
+                Err(_) => Err(E::invalid_value(Unexpected::Signed(n), &self)),
+            }
+        }
+
+        fn visit_i128<E>(self, n: i128) -> Result<T, E>
+        where
+            E: serde::de::Error,
+        {
+            match u128::try_from(n) {
+                Ok(n) => self.number(n),
+                Err(_) => Err(E::invalid_value(
+                    Unexpected::Other("negative integer"),
+                    &self,
+                )),
+            }
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<T, E>
+        where
+            E: serde::de::Error,
+        {
+            let trimmed = s.trim();
+            if let Some(hex) = trimmed
+                .strip_prefix("0x")
+                .or_else(|| trimmed.strip_prefix("0X"))
+            {
+                match u128::from_str_radix(hex, 16) {
+                    Ok(n) => self.number(n),
+                    Err(_) => Err(E::invalid_value(Unexpected::Str(s), &self)),
+                }
+            } else if !trimmed.is_empty() && trimmed.bytes().all(|b| b.is_ascii_digit()) {
+                match trimmed.parse::<u128>() {
+                    Ok(n) => self.number(n),
+                    Err(_) => Err(self.out_of_range()),
+                }
+            } else {
+                match S::decode(s) {
+                    Err(err) => Err(E::custom(err)),
+                    Ok(id) => (self.0)(id).ok_or_else(|| E::custom(Error::Overflow)),
+                }
+            }
+        }
+    }
+
+    let visitor = LenientVisitor::<S, _>(convert, PhantomData);
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        let id = S::Id::deserialize(deserializer)?;
+        (visitor.0)(id).ok_or_else(|| visitor.out_of_range())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{collections::HashMap, format, string::ToString, vec, vec::Vec};
+
+    use serde::{
+        de::{value, IntoDeserializer},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+    use serde_test::{
+        assert_de_tokens, assert_de_tokens_error, assert_tokens, Compact, Configure, Readable,
+        Token,
+    };
+
+    use crate::schemes::{
+        compound_phrase,
+        json::{from_json, to_json},
+        phrase, simple_phrase, var_phrase,
+    };
+
+    /// ID serialized with `lenient` module of the scheme.
+    #[derive(Debug, PartialEq)]
+    struct Lenient<T>(T);
+
+    macro_rules! lenient {
+        ($($id:ty => $scheme:ident),*) => {$(
+            impl Serialize for Lenient<$id> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    $scheme::lenient::serialize(&self.0, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for Lenient<$id> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    $scheme::lenient::deserialize(deserializer).map(Lenient)
+                }
+            }
+        )*};
+    }
+
+    lenient!(u8 => var_phrase, u32 => simple_phrase, u64 => phrase);
+
+    const EXPECTING: &str = "expected a phrase or a number";
+
+    #[test]
+    fn option_round_trips() {
+        let json = to_json(|s| phrase::option::serialize(&None::<u64>, s));
+        assert_eq!(json, "null");
+        let id: Option<u64> = from_json(&json, |d| phrase::option::deserialize(d)).unwrap();
+        assert_eq!(id, None);
+
+        let json = to_json(|s| phrase::option::serialize(&Some(42u64), s));
+        assert_eq!(json, format!("\"{}\"", phrase::encode(42)));
+        let id: Option<u64> = from_json(&json, |d| phrase::option::deserialize(d)).unwrap();
+        assert_eq!(id, Some(42));
+
+        let err = from_json(&format!("\"{}\"", var_phrase::encode(300)), |d| {
+            var_phrase::option::deserialize::<u8, _>(d)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("too large"), "{}", err);
+    }
+
+    #[test]
+    fn seq_round_trips() {
+        let ids = vec![0, 1, u64::MAX, 0x1234_5678_9abc_def0];
+        let json = to_json(|s| phrase::seq::serialize(&ids, s));
+        let phrases: Vec<_> = ids
+            .iter()
+            .map(|&id| phrase::encode(id).to_string())
+            .collect();
+        assert_eq!(json, format!("[\"{}\"]", phrases.join("\",\"")));
+        let decoded: Vec<u64> = from_json(&json, |d| phrase::seq::deserialize(d)).unwrap();
+        assert_eq!(decoded, ids);
+
+        let json = to_json(|s| phrase::seq::serialize(&Vec::<u64>::new(), s));
+        assert_eq!(json, "[]");
+        let decoded: Vec<u64> = from_json(&json, |d| phrase::seq::deserialize(d)).unwrap();
+        assert!(decoded.is_empty());
+    }
+
+    #[test]
+    fn keys_round_trip() {
+        let map: HashMap<u64, u32> = vec![(0, 1), (u64::MAX, 2), (42, 3)].into_iter().collect();
+        let json = to_json(|s| phrase::keys::serialize(&map, s));
+        for (&id, value) in &map {
+            assert!(
+                json.contains(&format!("\"{}\":{}", phrase::encode(id), value)),
+                "{}",
+                json
+            );
+        }
+        let decoded: HashMap<u64, u32> =
+            from_json(&json, |d| phrase::keys::deserialize(d)).unwrap();
+        assert_eq!(decoded, map);
+    }
+
+    #[test]
+    fn seq_fails_on_invalid_element() {
+        let json = format!(
+            "[\"{}\",\"not a phrase\",\"{}\"]",
+            phrase::encode(1),
+            phrase::encode(2)
+        );
+        let result = from_json(&json, |d| phrase::seq::deserialize::<Vec<u64>, _, _>(d));
+        assert!(result.is_err());
+
+        let json = format!(
+            "[\"{}\",\"{}\"]",
+            var_phrase::encode(1),
+            var_phrase::encode(300)
+        );
+        let err =
+            from_json(&json, |d| var_phrase::seq::deserialize::<Vec<u8>, u8, _>(d)).unwrap_err();
+        assert!(err.to_string().contains("too large"), "{}", err);
+    }
+
+    #[test]
+    fn keys_fail_on_invalid_entry() {
+        let json = format!(
+            "{{\"{}\":1,\"not a phrase\":2,\"{}\":3}}",
+            phrase::encode(1),
+            phrase::encode(2)
+        );
+        let result = from_json(&json, |d| {
+            phrase::keys::deserialize::<HashMap<u64, u32>, _, _, _>(d)
+        });
+        assert!(result.is_err());
+
+        let json = format!(
+            "{{\"{}\":1,\"{}\":\"two\"}}",
+            phrase::encode(1),
+            phrase::encode(2)
+        );
+        let result = from_json(&json, |d| {
+            phrase::keys::deserialize::<HashMap<u64, u32>, _, _, _>(d)
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn encode_errors_are_reported() {
+        let mut out = Vec::new();
+        let ids = vec![1u64, u64::MAX];
+        let result =
+            compound_phrase::seq::serialize(&ids, &mut serde_json::Serializer::new(&mut out));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn lenient_writes_phrases_in_readable_formats() {
+        let json = to_json(|s| phrase::lenient::serialize(&42u64, s));
+        assert_eq!(json, format!("\"{}\"", phrase::encode(42)));
+    }
+
+    #[test]
+    fn lenient_writes_integers_in_compact_formats() {
+        assert_tokens(&Lenient(42u64).compact(), &[Token::U64(42)]);
+        assert_tokens(&Lenient(u32::MAX).compact(), &[Token::U32(u32::MAX)]);
+    }
+
+    #[test]
+    fn lenient_reads_integers() {
+        assert_de_tokens(&Lenient(42u64).readable(), &[Token::U64(42)]);
+        assert_de_tokens(&Lenient(42u64).readable(), &[Token::I64(42)]);
+        assert_de_tokens(&Lenient(u64::MAX).readable(), &[Token::U64(u64::MAX)]);
+
+        let id = Lenient::<u64>::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+            42u128,
+        ));
+        assert_eq!(id, Ok(Lenient(42)));
+        let id = Lenient::<u64>::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+            42i128,
+        ));
+        assert_eq!(id, Ok(Lenient(42)));
+    }
+
+    #[test]
+    fn lenient_reads_decimal_strings() {
+        assert_de_tokens(&Lenient(42u64).readable(), &[Token::Str("42")]);
+        assert_de_tokens(&Lenient(42u64).readable(), &[Token::Str(" 42 ")]);
+        assert_de_tokens(&Lenient(0u64).readable(), &[Token::Str("0")]);
+        assert_de_tokens(
+            &Lenient(u64::MAX).readable(),
+            &[Token::Str("18446744073709551615")],
+        );
+    }
+
+    #[test]
+    fn lenient_reads_hex_strings() {
+        assert_de_tokens(&Lenient(42u64).readable(), &[Token::Str("0x2a")]);
+        assert_de_tokens(&Lenient(42u64).readable(), &[Token::Str("0X2A")]);
+        assert_de_tokens(
+            &Lenient(u64::MAX).readable(),
+            &[Token::Str("0xFFFFFFFFFFFFFFFF")],
+        );
+        assert_de_tokens_error::<Readable<Lenient<u64>>>(
+            &[Token::Str("0x")],
+            &format!("invalid value: string \"0x\", {}", EXPECTING),
+        );
+        assert_de_tokens_error::<Readable<Lenient<u64>>>(
+            &[Token::Str("0xfoo")],
+            &format!("invalid value: string \"0xfoo\", {}", EXPECTING),
+        );
+    }
+
+    #[test]
+    fn lenient_reads_phrases() {
+        for id in [0, 42, u64::MAX] {
+            let phrase = phrase::encode(id);
+            let json = format!("\"{}\"", phrase);
+            assert_eq!(
+                from_json(&json, |d| Lenient::<u64>::deserialize(d)).unwrap(),
+                Lenient(id)
+            );
+            let json = format!("\"{}\"", phrase.hyphenated());
+            assert_eq!(
+                from_json(&json, |d| Lenient::<u64>::deserialize(d)).unwrap(),
+                Lenient(id)
+            );
+        }
+
+        assert!(from_json("\"not a phrase\"", |d| Lenient::<u64>::deserialize(d)).is_err());
+    }
+
+    #[test]
+    fn lenient_rejects_negative_integers() {
+        assert_de_tokens_error::<Readable<Lenient<u64>>>(
+            &[Token::I64(-1)],
+            &format!("invalid value: integer `-1`, {}", EXPECTING),
+        );
+
+        let err = Lenient::<u64>::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+            -1i128,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("invalid value: negative integer, {}", EXPECTING)
+        );
+    }
+
+    #[test]
+    fn lenient_rejects_numbers_wider_than_scheme() {
+        let out_of_range = format!("invalid value: number out of range, {}", EXPECTING);
+
+        assert_de_tokens(
+            &Lenient(u32::MAX).readable(),
+            &[Token::U64(u32::MAX.into())],
+        );
+        assert_de_tokens_error::<Readable<Lenient<u32>>>(&[Token::U64(1 << 32)], &out_of_range);
+        assert_de_tokens_error::<Readable<Lenient<u32>>>(
+            &[Token::Str("4294967296")],
+            &out_of_range,
+        );
+        assert_de_tokens_error::<Readable<Lenient<u32>>>(
+            &[Token::Str("0x100000000")],
+            &out_of_range,
+        );
+        assert_de_tokens_error::<Readable<Lenient<u64>>>(
+            &[Token::Str("340282366920938463463374607431768211456")],
+            &out_of_range,
+        );
+
+        let err = Lenient::<u64>::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+            1u128 << 64,
+        ))
+        .unwrap_err();
+        assert_eq!(err.to_string(), out_of_range);
+
+        // Fits into the scheme but not into the field type.
+        assert_de_tokens_error::<Readable<Lenient<u8>>>(&[Token::U64(256)], &out_of_range);
+        let json = format!("\"{}\"", var_phrase::encode(256));
+        assert!(from_json(&json, |d| Lenient::<u8>::deserialize(d)).is_err());
+    }
+
+    #[test]
+    fn lenient_reads_only_integers_in_compact_formats() {
+        assert_de_tokens(&Lenient(42u64).compact(), &[Token::U64(42)]);
+        assert_de_tokens(&Lenient(42u8).compact(), &[Token::U64(42)]);
+        assert_de_tokens_error::<Compact<Lenient<u8>>>(
+            &[Token::U64(256)],
+            &format!("invalid value: number out of range, {}", EXPECTING),
+        );
+        assert_de_tokens_error::<Compact<Lenient<u64>>>(
+            &[Token::Str("42")],
+            "invalid type: string \"42\", expected u64",
+        );
+    }
+}